@@ -0,0 +1,651 @@
+mod report;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use financial_planner_core::log_file::LogFile;
+use financial_planner_core::*;
+use std::{error::Error, path::PathBuf, process::ExitCode};
+
+/// Manage a financial planner log file from the terminal. Reads and writes
+/// the same YAML format the web app exports.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// path to the log file; created on first write if it does not exist
+    #[arg(short, long, default_value = "log.yaml")]
+    file: PathBuf,
+    /// passphrase of an encrypted log; a new file is encrypted if given
+    #[arg(long, env = "FINANCIAL_PLANNER_PASSPHRASE", hide_env_values = true)]
+    passphrase: Option<String>,
+    /// work in a what-if scenario: changes only apply to it, and reports
+    /// show the log as it would be in it
+    #[arg(long, global = true)]
+    scenario: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// record a new transaction
+    Add {
+        kind: Kind,
+        #[arg(value_parser = clap::value_parser!(Dollars).range(0..))]
+        value: Dollars,
+        date: Date,
+        /// who was paid, or who paid
+        #[arg(long, default_value = "")]
+        payee: String,
+        #[arg(long, default_value = "")]
+        memo: String,
+        /// a cheque or invoice number, say
+        #[arg(long)]
+        reference: Option<String>,
+        /// left out to have the rules fill it in
+        #[arg(long, default_value = "")]
+        category: String,
+        /// record an estimate rather than something that has happened
+        #[arg(long, conflicts_with = "fulfils")]
+        planned: bool,
+        /// the id of the planned transaction this one fulfils
+        #[arg(long)]
+        fulfils: Option<TransactionId>,
+    },
+    /// delete the transaction with the given id
+    Delete { id: TransactionId },
+    /// tick transactions off against a bank statement
+    Clear {
+        #[arg(required = true)]
+        ids: Vec<TransactionId>,
+        /// untick them instead
+        #[arg(long)]
+        undo: bool,
+    },
+    /// compare the cleared transactions with a bank statement's ending
+    /// balance, and reconcile them, locking them, if they match
+    Reconcile {
+        date: Date,
+        #[arg(allow_negative_numbers = true)]
+        balance: Dollars,
+    },
+    /// record a transaction that repeats from its date onwards
+    Schedule {
+        kind: Kind,
+        #[arg(value_parser = clap::value_parser!(Dollars).range(0..))]
+        value: Dollars,
+        date: Date,
+        #[arg(long, value_enum)]
+        every: Every,
+        /// the last date it can fall on
+        #[arg(long)]
+        until: Option<Date>,
+        /// grow the amount with the inflation set by `inflation`
+        #[arg(long)]
+        indexed: bool,
+    },
+    /// delete the scheduled transaction with the given id
+    Unschedule { id: ScheduleId },
+    /// list the scheduled transactions
+    Schedules,
+    /// add the monthly payments of a loan or mortgage, each split into
+    /// principal and interest; prints the amortization schedule
+    Loan {
+        /// the lender, who the payments are to
+        name: String,
+        #[arg(value_parser = clap::value_parser!(Dollars).range(1..))]
+        principal: Dollars,
+        /// the yearly interest rate as a percentage, e.g. 6.5
+        rate: f64,
+        /// the number of monthly payments
+        term_months: u32,
+        first_payment: Date,
+        /// paid off the principal on top of every payment
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(Dollars).range(0..))]
+        extra: Dollars,
+        /// a one-off extra payment, as DATE=AMOUNT, made with the first
+        /// payment on or after DATE; may be repeated
+        #[arg(long, value_parser = parse_lump_sum)]
+        lump_sum: Vec<(Date, Dollars)>,
+        /// only print the schedule, without adding the payments
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// categorize transactions added from now on whose payee contains
+    /// some text, ignoring case; prints the existing transactions it
+    /// would have changed
+    Rule {
+        payee_contains: String,
+        category: String,
+        /// also make matching transactions this kind
+        #[arg(long, value_enum)]
+        kind: Option<Kind>,
+        /// only print what it would have changed, without adding it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// delete the categorization rule with the given id
+    Unrule { id: RuleId },
+    /// list the categorization rules
+    Rules,
+    /// set the interest the balance earns, and is charged while
+    /// overdrawn; prints the current setting if no rate is given
+    Interest {
+        /// the yearly percentage earned on a positive balance
+        savings_rate: Option<f64>,
+        /// the yearly percentage charged on a negative balance, as on a
+        /// credit line
+        #[arg(long, default_value_t = 0.0)]
+        credit_rate: f64,
+        #[arg(long, value_enum, default_value_t = Basis::Apy)]
+        basis: Basis,
+        #[arg(long, value_enum, default_value_t = Compounds::Monthly)]
+        compounding: Compounds,
+        /// stop accruing interest
+        #[arg(long, conflicts_with = "savings_rate")]
+        off: bool,
+    },
+    /// set the yearly inflation rate that indexed schedules grow with;
+    /// prints the current setting if no rate is given
+    Inflation {
+        /// the yearly percentage, e.g. 2.5
+        #[arg(allow_negative_numbers = true)]
+        rate: Option<f64>,
+        /// a different rate for one year, as YEAR=RATE; may be repeated
+        #[arg(long, value_parser = parse_year_rate, requires = "rate")]
+        year: Vec<(i32, f64)>,
+        /// stop growing indexed schedules
+        #[arg(long, conflicts_with = "rate")]
+        off: bool,
+    },
+    /// list the current transactions
+    List,
+    /// print the income, expenses and balance of each day, or of each
+    /// longer period
+    Timeline {
+        #[arg(long)]
+        from: Option<Date>,
+        #[arg(long)]
+        to: Option<Date>,
+        #[arg(long, value_enum, default_value_t = Period::Day)]
+        by: Period,
+        /// the first day of a week when grouping by week
+        #[arg(long, value_enum, default_value_t = WeekStart::Monday)]
+        week_start: WeekStart,
+        /// project past the last transaction from the schedules and
+        /// recent history
+        #[arg(long)]
+        forecast: bool,
+        /// days of history averaged into the forecast
+        #[arg(long, default_value_t = 90)]
+        trailing_days: u32,
+        /// deflate every amount to today's dollars by the inflation set
+        #[arg(long)]
+        todays_dollars: bool,
+    },
+    /// print planned against actual amounts by period and category
+    Variance {
+        #[arg(long)]
+        from: Option<Date>,
+        #[arg(long)]
+        to: Option<Date>,
+        #[arg(long, value_enum, default_value_t = Period::Month)]
+        by: Period,
+        /// the first day of a week when grouping by week
+        #[arg(long, value_enum, default_value_t = WeekStart::Monday)]
+        week_start: WeekStart,
+    },
+    /// start a what-if scenario branched off the log
+    Fork { name: String },
+    /// drop a what-if scenario and its changes
+    Drop { name: String },
+    /// list the what-if scenarios with where each leaves the balance
+    Scenarios,
+    /// print totals over a date range and warn if the balance runs low
+    Summary {
+        #[arg(long)]
+        from: Option<Date>,
+        #[arg(long)]
+        to: Option<Date>,
+        /// also warn when the balance falls below this
+        #[arg(long, default_value_t = 0)]
+        warn_below: Dollars,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Kind {
+    Income,
+    Expense,
+}
+
+impl From<Kind> for TransactionKind {
+    fn from(value: Kind) -> Self {
+        match value {
+            Kind::Income => TransactionKind::Income,
+            Kind::Expense => TransactionKind::Expense,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Every {
+    Week,
+    Fortnight,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl From<Every> for Frequency {
+    fn from(value: Every) -> Self {
+        match value {
+            Every::Week => Frequency::Weekly,
+            Every::Fortnight => Frequency::Fortnightly,
+            Every::Month => Frequency::Monthly,
+            Every::Quarter => Frequency::Quarterly,
+            Every::Year => Frequency::Yearly,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Basis {
+    Apr,
+    Apy,
+}
+
+impl From<Basis> for RateBasis {
+    fn from(value: Basis) -> Self {
+        match value {
+            Basis::Apr => RateBasis::Apr,
+            Basis::Apy => RateBasis::Apy,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Compounds {
+    Daily,
+    Monthly,
+}
+
+impl From<Compounds> for Compounding {
+    fn from(value: Compounds) -> Self {
+        match value {
+            Compounds::Daily => Compounding::Daily,
+            Compounds::Monthly => Compounding::Monthly,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Period {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum WeekStart {
+    Monday,
+    Sunday,
+}
+
+impl Period {
+    fn granularity(self, week_start: WeekStart) -> Granularity {
+        match self {
+            Period::Day => Granularity::Day,
+            Period::Week => Granularity::Week(match week_start {
+                WeekStart::Monday => chrono::Weekday::Mon,
+                WeekStart::Sunday => chrono::Weekday::Sun,
+            }),
+            Period::Month => Granularity::Month,
+            Period::Quarter => Granularity::Quarter,
+            Period::Year => Granularity::Year,
+        }
+    }
+}
+
+fn parse_lump_sum(s: &str) -> Result<(Date, Dollars), String> {
+    let (date, value) = s.split_once('=').ok_or("expected DATE=AMOUNT")?;
+    let date = date.parse::<Date>().map_err(|e| e.to_string())?;
+    match value.parse::<Dollars>() {
+        Ok(value) if value > 0 => Ok((date, value)),
+        _ => Err(format!("{value} is not a positive amount")),
+    }
+}
+
+fn parse_year_rate(s: &str) -> Result<(i32, f64), String> {
+    let (year, rate) = s.split_once('=').ok_or("expected YEAR=RATE")?;
+    let year = year.parse::<i32>().map_err(|e| e.to_string())?;
+    match rate.parse::<f64>() {
+        Ok(rate) if Inflation::is_valid_rate(rate) => Ok((year, rate)),
+        _ => Err(format!("{rate} is not a valid rate")),
+    }
+}
+
+/// the log's current date range with either end optionally overridden
+fn date_range(
+    log: &Log,
+    from: Option<Date>,
+    to: Option<Date>,
+) -> Result<DateRange, DateRangeError> {
+    let date_range = log.date_range();
+    DateRange::new(
+        from.unwrap_or(date_range.start()),
+        to.unwrap_or(date_range.end()),
+    )
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let mut log_file = LogFile::open(cli.file, cli.passphrase.as_deref())?;
+    // what the commands read: the log, or the scenario being worked in
+    let log = match &cli.scenario {
+        Some(_)
+            if matches!(
+                cli.command,
+                Command::Clear { .. }
+                    | Command::Reconcile { .. }
+                    | Command::Fork { .. }
+                    | Command::Drop { .. }
+                    | Command::Scenarios
+            ) =>
+        {
+            return Err("--scenario can't be used with this command".into());
+        }
+        Some(name) => log_file
+            .log
+            .scenario(name)
+            .ok_or_else(|| format!("no scenario named {name}"))??,
+        None => log_file.log.clone(),
+    };
+    let branch = |entry| match &cli.scenario {
+        Some(name) => Entry::Branch(name.clone(), Box::new(entry)),
+        None => entry,
+    };
+    match cli.command {
+        Command::Add {
+            kind,
+            value,
+            date,
+            payee,
+            memo,
+            reference,
+            category,
+            planned,
+            fulfils,
+        } => {
+            if let Some(plan) = fulfils.filter(|plan| !log.is_open_plan(*plan)) {
+                return Err(format!("no unfulfilled planned transaction with id {plan}").into());
+            }
+            let transaction = Transaction {
+                payee,
+                memo,
+                reference,
+                category,
+                planned,
+                fulfils,
+                ..Transaction::new(value, kind.into(), date)
+            };
+            let transaction = categorize(&log.rule_records(), transaction);
+            log_file.log.append(branch(Entry::Create(transaction)))?;
+            log_file.save()?;
+        }
+        Command::Delete { id } => {
+            if !log.transaction_records().iter().any(|tr| tr.id == id) {
+                return Err(format!("no transaction with id {id}").into());
+            }
+            if log.status(id) == TransactionStatus::Reconciled {
+                return Err(format!("transaction {id} is reconciled").into());
+            }
+            log_file.log.append(branch(Entry::Delete(id)))?;
+            log_file.save()?;
+        }
+        Command::Clear { ids, undo } => {
+            let records = log.transaction_records();
+            for id in &ids {
+                match records.iter().find(|tr| tr.id == *id) {
+                    None => return Err(format!("no transaction with id {id}").into()),
+                    Some(tr) if tr.status == TransactionStatus::Reconciled => {
+                        return Err(format!("transaction {id} is reconciled").into())
+                    }
+                    Some(_) => {}
+                }
+            }
+            for id in ids {
+                log_file.log.append(Entry::SetCleared(id, !undo))?;
+            }
+            log_file.save()?;
+        }
+        Command::Reconcile { date, balance } => {
+            let statement = Reconciliation {
+                statement_date: date,
+                statement_balance: balance,
+            };
+            print!("{}", report::reconciliation(&log, &statement));
+            let difference = log.unreconciled_difference(&statement);
+            if difference != 0 {
+                return Err(format!(
+                    "off by {difference}; clear the transactions on the statement first"
+                )
+                .into());
+            }
+            log_file.log.append(Entry::Reconcile(statement))?;
+            log_file.save()?;
+            println!("Reconciled.");
+        }
+        Command::Schedule {
+            kind,
+            value,
+            date,
+            every,
+            until,
+            indexed,
+        } => {
+            if until.is_some_and(|until| until < date) {
+                return Err(format!("--until {} is before {date}", until.unwrap()).into());
+            }
+            log_file.log.append(branch(Entry::Schedule(Schedule {
+                transaction: Transaction::new(value, kind.into(), date),
+                frequency: every.into(),
+                until,
+                indexed,
+            })))?;
+            log_file.save()?;
+        }
+        Command::Unschedule { id } => {
+            if !log.schedule_records().iter().any(|sr| sr.id == id) {
+                return Err(format!("no scheduled transaction with id {id}").into());
+            }
+            log_file.log.append(branch(Entry::Unschedule(id)))?;
+            log_file.save()?;
+        }
+        Command::Loan {
+            name,
+            principal,
+            rate,
+            term_months,
+            first_payment,
+            extra,
+            lump_sum,
+            dry_run,
+        } => {
+            let loan = Loan {
+                name,
+                principal,
+                annual_rate: rate,
+                term_months,
+                first_payment,
+                extra,
+                lump_sums: lump_sum,
+            };
+            let payment = loan.regular_payment()?;
+            print!(
+                "{}",
+                report::loan_schedule(&loan, payment, &loan.schedule()?)
+            );
+            if !dry_run {
+                for t in loan.transactions()? {
+                    log_file.log.append(branch(Entry::Create(t)))?;
+                }
+                log_file.save()?;
+            }
+        }
+        Command::Schedules => {
+            print!("{}", report::schedules_list(&log.schedule_records()));
+        }
+        Command::Rule {
+            payee_contains,
+            category,
+            kind,
+            dry_run,
+        } => {
+            if payee_contains.trim().is_empty() {
+                return Err("a rule needs some payee text to match".into());
+            }
+            let rule = Rule {
+                payee_contains,
+                category,
+                kind: kind.map(Into::into),
+            };
+            let records = log.transaction_records();
+            print!("{}", report::rule_preview(&rule.preview(&records)));
+            if !dry_run {
+                log_file.log.append(branch(Entry::AddRule(rule)))?;
+                log_file.save()?;
+            }
+        }
+        Command::Unrule { id } => {
+            if !log.rule_records().iter().any(|rr| rr.id == id) {
+                return Err(format!("no categorization rule with id {id}").into());
+            }
+            log_file.log.append(branch(Entry::RemoveRule(id)))?;
+            log_file.save()?;
+        }
+        Command::Rules => {
+            print!("{}", report::rules_list(&log.rule_records()));
+        }
+        Command::Interest {
+            savings_rate,
+            credit_rate,
+            basis,
+            compounding,
+            off,
+        } => {
+            let interest = match savings_rate {
+                _ if off => None,
+                None => {
+                    print!("{}", report::interest(log.interest()));
+                    return Ok(());
+                }
+                // the log rejects rates out of range
+                Some(savings_rate) => Some(Interest {
+                    savings_rate,
+                    credit_rate,
+                    basis: basis.into(),
+                    compounding: compounding.into(),
+                }),
+            };
+            log_file.log.append(branch(Entry::SetInterest(interest)))?;
+            log_file.save()?;
+        }
+        Command::Inflation { rate, year, off } => {
+            let inflation = match rate {
+                _ if off => None,
+                None => {
+                    print!("{}", report::inflation(log.inflation()));
+                    return Ok(());
+                }
+                Some(rate) => Some(Inflation {
+                    rate,
+                    by_year: year.into_iter().collect(),
+                }),
+            };
+            log_file
+                .log
+                .append(branch(Entry::SetInflation(inflation)))?;
+            log_file.save()?;
+        }
+        Command::List => {
+            print!("{}", report::transactions_list(&log.transaction_records()));
+        }
+        Command::Timeline {
+            from,
+            to,
+            by,
+            week_start,
+            forecast,
+            trailing_days,
+            todays_dollars,
+        } => {
+            let inflation = match log.inflation() {
+                None if todays_dollars => {
+                    return Err("no inflation is set; see the inflation command".into());
+                }
+                inflation => inflation.filter(|_| todays_dollars),
+            };
+            let date_range = date_range(&log, from, to)?;
+            let granularity = by.granularity(week_start);
+            let mut data = if forecast {
+                log.forecast_data_by(&date_range, granularity, trailing_days)
+            } else {
+                log.timeline_data_by(&date_range, granularity)
+            };
+            if let Some(inflation) = inflation {
+                data = data.in_dollars_of(inflation, chrono::Local::now().date_naive());
+            }
+            print!("{}", report::timeline(&data));
+            if let Some(baseline) = log.baseline(trailing_days).filter(|_| forecast) {
+                print!("\n{}", report::baseline(&baseline));
+            }
+        }
+        Command::Variance {
+            from,
+            to,
+            by,
+            week_start,
+        } => {
+            let date_range = date_range(&log, from, to)?;
+            let data = log.timeline_data_by(&date_range, by.granularity(week_start));
+            print!("{}", report::variance(&log.variance(&data)));
+        }
+        Command::Fork { name } => {
+            if log.scenario_names().contains(&name) {
+                return Err(format!("there is already a scenario named {name}").into());
+            }
+            log_file.log.append(Entry::Fork(name))?;
+            log_file.save()?;
+        }
+        Command::Drop { name } => {
+            if !log.scenario_names().contains(&name) {
+                return Err(format!("no scenario named {name}").into());
+            }
+            log_file.log.append(Entry::DropScenario(name))?;
+            log_file.save()?;
+        }
+        Command::Scenarios => {
+            print!("{}", report::scenarios(&log));
+        }
+        Command::Summary {
+            from,
+            to,
+            warn_below,
+        } => {
+            let date_range = date_range(&log, from, to)?;
+            let data = log.timeline_data_in(&date_range);
+            print!("{}", report::summary(&date_range, &data, warn_below));
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
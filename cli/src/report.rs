@@ -0,0 +1,757 @@
+use financial_planner_core::*;
+use std::fmt::Write;
+
+/// one line per transaction, as shown by the web app's transactions list
+pub fn transactions_list(transaction_records: &[TransactionRecord]) -> String {
+    let mut out = format!(
+        "{:>5}  {:<7}  {:<10}  {:>10}  {:<10}  {}\n",
+        "ID", "KIND", "DATE", "VALUE", "STATUS", "DETAILS"
+    );
+    for tr in transaction_records {
+        let Transaction {
+            value,
+            kind,
+            date,
+            payee,
+            memo,
+            reference,
+            category,
+            planned,
+            fulfils,
+        } = &tr.transaction;
+        let details = [
+            payee.clone(),
+            Some(category)
+                .filter(|c| !c.is_empty())
+                .map(|c| format!("[{c}]"))
+                .unwrap_or_default(),
+            reference
+                .as_ref()
+                .map(|r| format!("#{r}"))
+                .unwrap_or_default(),
+            memo.clone(),
+            fulfils
+                .map(|plan| format!("fulfils plan {plan}"))
+                .unwrap_or_default(),
+            tr.fulfilled_by
+                .map(|actual| format!("fulfilled by {actual}"))
+                .unwrap_or_default(),
+        ]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("  ");
+        let status = match tr.status {
+            TransactionStatus::Uncleared if *planned => "Planned".to_owned(),
+            TransactionStatus::Uncleared => "-".to_owned(),
+            status => status.to_string(),
+        };
+        let line = format!(
+            "{:>5}  {:<7}  {:<10}  {:>10}  {:<10}  {details}",
+            tr.id,
+            kind.to_string(),
+            date.to_string(),
+            value,
+            status
+        );
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+    out
+}
+
+/// a single date, or `start..end` for longer periods
+fn period(period: &DateRange) -> String {
+    if period.num_days() == 1 {
+        period.start().to_string()
+    } else {
+        format!("{}..{}", period.start(), period.end())
+    }
+}
+
+/// one line per date summary, as shown by the web app's timeline text
+/// view, with an interest column if any was paid or charged
+pub fn timeline(data: &TimelineData) -> String {
+    let width = data
+        .iter()
+        .map(|s| period(&s.period).len())
+        .max()
+        .unwrap_or_default()
+        .max(10);
+    let with_interest = data.iter().any(|s| s.interest != 0);
+    let mut out = format!(
+        "{:<width$}  {:>10}  {:>10}  {:>10}",
+        "DATE", "INCOME", "EXPENSES", "BALANCE"
+    );
+    if with_interest {
+        write!(out, "  {:>10}", "INTEREST").unwrap();
+    }
+    out.push('\n');
+    for DateSummary {
+        period: p,
+        income,
+        expenses,
+        balance,
+        interest,
+        forecast,
+    } in data.iter()
+    {
+        write!(
+            out,
+            "{:<width$}  {:>10}  {:>10}  {:>10}",
+            period(p),
+            income,
+            expenses,
+            balance
+        )
+        .unwrap();
+        if with_interest {
+            write!(out, "  {:>10}", interest).unwrap();
+        }
+        writeln!(out, "{}", if *forecast { "  forecast" } else { "" }).unwrap();
+    }
+    out
+}
+
+/// planned against actual amounts, one line per period and category
+pub fn variance(data: &[Variance]) -> String {
+    let width = data
+        .iter()
+        .map(|v| period(&v.period).len())
+        .max()
+        .unwrap_or_default()
+        .max(10);
+    let mut out = format!(
+        "{:<width$}  {:<20}  {:>10}  {:>10}  {:>10}\n",
+        "PERIOD", "CATEGORY", "PLANNED", "ACTUAL", "VARIANCE"
+    );
+    for v in data {
+        writeln!(
+            out,
+            "{:<width$}  {:<20}  {:>10}  {:>10}  {:>10}",
+            period(&v.period),
+            if v.category.is_empty() {
+                "-"
+            } else {
+                &v.category
+            },
+            v.planned,
+            v.actual,
+            v.variance()
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// the average income and expenses a day of each category that the
+/// forecast carries on
+pub fn baseline(baseline: &Baseline) -> String {
+    let mut out = format!(
+        "Baseline over {}\n{:<20}  {:>12}  {:>12}\n",
+        period(&baseline.window),
+        "CATEGORY",
+        "INCOME/DAY",
+        "EXPENSES/DAY"
+    );
+    for (category, income, expenses) in baseline.daily_averages() {
+        writeln!(
+            out,
+            "{:<20}  {:>12.2}  {:>12.2}",
+            if category.is_empty() { "-" } else { category },
+            income,
+            expenses
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// one line per scheduled transaction
+pub fn schedules_list(schedule_records: &[ScheduleRecord]) -> String {
+    let mut out = format!(
+        "{:>5}  {:<7}  {:<10}  {:>10}  {:<11}  {:<10}\n",
+        "ID", "KIND", "FROM", "VALUE", "EVERY", "UNTIL"
+    );
+    for ScheduleRecord { schedule, id } in schedule_records {
+        let Transaction {
+            value, kind, date, ..
+        } = &schedule.transaction;
+        writeln!(
+            out,
+            "{:>5}  {:<7}  {:<10}  {:>10}  {:<11}  {}{}",
+            id,
+            kind.to_string(),
+            date.to_string(),
+            value,
+            schedule.frequency.to_string(),
+            schedule
+                .until
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "-".to_owned()),
+            if schedule.indexed {
+                "  grows with inflation"
+            } else {
+                ""
+            }
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// one line per payment on a loan, with how long it takes to pay off at
+/// `payment` a month
+pub fn loan_schedule(loan: &Loan, payment: Dollars, schedule: &[LoanPayment]) -> String {
+    let mut out = format!(
+        "{:>5}  {:<10}  {:>10}  {:>10}  {:>10}\n",
+        "#", "DATE", "PRINCIPAL", "INTEREST", "BALANCE"
+    );
+    for p in schedule {
+        writeln!(
+            out,
+            "{:>5}  {:<10}  {:>10}  {:>10}  {:>10}",
+            p.number,
+            p.date.to_string(),
+            p.principal,
+            p.interest,
+            p.balance
+        )
+        .unwrap();
+    }
+    let interest: i64 = schedule.iter().map(|p| p.interest as i64).sum();
+    writeln!(
+        out,
+        "Payment:  {payment}{}\nPayoff:   {}\nInterest: {interest}",
+        if loan.extra > 0 {
+            format!(" plus {}", loan.extra)
+        } else {
+            String::new()
+        },
+        schedule
+            .last()
+            .map(|p| p.date.to_string())
+            .unwrap_or_default()
+    )
+    .unwrap();
+    out
+}
+
+/// the interest the balance earns and is charged
+pub fn interest(interest: Option<Interest>) -> String {
+    match interest {
+        Some(i) => format!(
+            "Savings:     {}% {}\nOverdrawn:   {}% {}\nCompounding: {}\n",
+            i.savings_rate, i.basis, i.credit_rate, i.basis, i.compounding
+        ),
+        None => "No interest.\n".to_owned(),
+    }
+}
+
+/// the inflation rate, and any years with a rate of their own
+pub fn inflation(inflation: Option<&Inflation>) -> String {
+    let Some(inflation) = inflation else {
+        return "No inflation.\n".to_owned();
+    };
+    let mut out = format!("Inflation: {}% a year\n", inflation.rate);
+    for (year, rate) in &inflation.by_year {
+        writeln!(out, "{year:>10}: {rate}%").unwrap();
+    }
+    out
+}
+
+/// one line per categorization rule
+pub fn rules_list(rule_records: &[RuleRecord]) -> String {
+    let mut out = format!(
+        "{:>5}  {:<20}  {:<20}  {}\n",
+        "ID", "PAYEE", "CATEGORY", "KIND"
+    );
+    for RuleRecord { rule, id } in rule_records {
+        writeln!(
+            out,
+            "{:>5}  {:<20}  {:<20}  {}",
+            id,
+            rule.payee_contains,
+            rule.category,
+            rule.kind
+                .map(|k| k.to_string())
+                .unwrap_or_else(|| "-".to_owned())
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// the existing transactions a rule would change, as it would leave them
+pub fn rule_preview(preview: &[(&TransactionRecord, Transaction)]) -> String {
+    if preview.is_empty() {
+        return "Would not change any existing transactions.\n".to_owned();
+    }
+    let changed: Vec<TransactionRecord> = preview
+        .iter()
+        .map(|(tr, t)| (tr.id, t.clone()).into())
+        .collect();
+    format!(
+        "Would have changed {} existing transactions to:\n{}",
+        changed.len(),
+        transactions_list(&changed)
+    )
+}
+
+/// how far the cleared transactions are from a statement, and the
+/// transactions that could still be cleared against it
+pub fn reconciliation(log: &Log, statement: &Reconciliation) -> String {
+    let mut out = format!(
+        "Statement:  {} on {}\nCleared:    {}\nDifference: {}\n",
+        statement.statement_balance,
+        statement.statement_date,
+        log.cleared_balance(statement.statement_date),
+        log.unreconciled_difference(statement)
+    );
+    let open: Vec<TransactionRecord> = log
+        .transaction_records()
+        .into_iter()
+        .filter(|tr| {
+            tr.transaction.date <= statement.statement_date
+                && tr.status != TransactionStatus::Reconciled
+                && !tr.transaction.planned
+        })
+        .collect();
+    if !open.is_empty() {
+        out.push_str(&transactions_list(&open));
+    }
+    out
+}
+
+/// one line per what-if scenario, with its balance at the end of the
+/// log's date range and how far that is from the log's own
+pub fn scenarios(log: &Log) -> String {
+    let balance = |log: &Log| {
+        log.timeline_data_in(&log.date_range())
+            .iter()
+            .last()
+            .map(|s| s.balance as i64)
+            .unwrap_or_default()
+    };
+    let baseline = balance(log);
+    let mut out = format!(
+        "{:<20}  {:>7}  {:>10}  {:>10}\n",
+        "SCENARIO", "CHANGES", "BALANCE", "DIFFERENCE"
+    );
+    for name in log.scenario_names() {
+        let (Some(entries), Some(scenario)) = (log.scenario_entries(&name), log.scenario(&name))
+        else {
+            continue;
+        };
+        let scenario = match scenario {
+            Ok(scenario) => scenario,
+            Err(e) => {
+                writeln!(out, "{:<20}  {:>7}  {e}", name, entries.len()).unwrap();
+                continue;
+            }
+        };
+        let scenario_balance = balance(&scenario);
+        writeln!(
+            out,
+            "{:<20}  {:>7}  {:>10}  {:>10}",
+            name,
+            entries.len(),
+            scenario_balance,
+            scenario_balance - baseline
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// totals over the range covered by `data`, and where the balance runs
+/// low
+pub fn summary(date_range: &DateRange, data: &TimelineData, threshold: Dollars) -> String {
+    let income: i64 = data.iter().map(|s| s.income as i64).sum();
+    let expenses: i64 = data.iter().map(|s| s.expenses as i64).sum();
+    let balance = data.iter().last().map(|s| s.balance).unwrap_or_default();
+    let mut out = format!(
+        "Range:    {} to {}\nIncome:   {income}\nExpenses: {expenses}\nNet:      {}\nBalance:  {balance}\n",
+        date_range.start(),
+        date_range.end(),
+        income - expenses
+    );
+    let low_balance = data.low_balance(threshold);
+    if let Some(s) = low_balance.minimum {
+        writeln!(out, "Lowest:   {} on {}", s.balance, s.period.start()).unwrap();
+    }
+    if let Some(s) = low_balance.below_zero {
+        writeln!(out, "Warning:  overdrawn from {}", s.period.start()).unwrap();
+    }
+    if let Some(s) = low_balance.below_threshold.filter(|_| threshold != 0) {
+        writeln!(out, "Warning:  below {threshold} from {}", s.period.start()).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        baseline, inflation, interest, loan_schedule, reconciliation, rule_preview, rules_list,
+        scenarios, schedules_list, summary, timeline, transactions_list, variance,
+    };
+    use chrono::Weekday;
+    use financial_planner_core::*;
+
+    fn log() -> Log {
+        Log::from(vec![
+            Entry::SetDate(
+                DateRange::new("2023-01-01".parse().unwrap(), "2023-01-03".parse().unwrap())
+                    .unwrap(),
+            ),
+            Entry::Create(Transaction {
+                payee: "Employer".to_owned(),
+                reference: Some("INV-7".to_owned()),
+                category: "Salary".to_owned(),
+                ..Transaction::new(100, TransactionKind::Income, "2023-01-02".parse().unwrap())
+            }),
+            Entry::Create(Transaction {
+                memo: "lunch".to_owned(),
+                ..Transaction::new(30, TransactionKind::Expense, "2023-01-03".parse().unwrap())
+            }),
+        ])
+    }
+
+    #[test]
+    fn test_transactions_list() {
+        assert_eq!(
+            transactions_list(&log().transaction_records()),
+            concat!(
+                "   ID  KIND     DATE             VALUE  STATUS      DETAILS\n",
+                "    0  Income   2023-01-02         100  -           Employer  [Salary]  #INV-7\n",
+                "    1  Expense  2023-01-03          30  -           lunch\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut log = log();
+        log.append(Entry::Create(Transaction {
+            payee: "NETFLIX.COM".to_owned(),
+            ..Transaction::new(15, TransactionKind::Income, "2023-01-03".parse().unwrap())
+        }))
+        .unwrap();
+        let rule = Rule {
+            payee_contains: "netflix".to_owned(),
+            category: "Subscriptions".to_owned(),
+            kind: Some(TransactionKind::Expense),
+        };
+        let records = log.transaction_records();
+        assert_eq!(
+            rule_preview(&rule.preview(&records)),
+            concat!(
+                "Would have changed 1 existing transactions to:\n",
+                "   ID  KIND     DATE             VALUE  STATUS      DETAILS\n",
+                "    2  Expense  2023-01-03          15  -           NETFLIX.COM  [Subscriptions]\n",
+            )
+        );
+        log.append(Entry::AddRule(rule)).unwrap();
+        assert_eq!(
+            rules_list(&log.rule_records()),
+            concat!(
+                "   ID  PAYEE                 CATEGORY              KIND\n",
+                "    0  netflix               Subscriptions         Expense\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_reconciliation() {
+        let mut log = log();
+        let statement = Reconciliation {
+            statement_date: "2023-01-02".parse().unwrap(),
+            statement_balance: 100,
+        };
+        log.append(Entry::SetCleared(0, true)).unwrap();
+        assert_eq!(
+            reconciliation(&log, &statement),
+            concat!(
+                "Statement:  100 on 2023-01-02\n",
+                "Cleared:    100\n",
+                "Difference: 0\n",
+                "   ID  KIND     DATE             VALUE  STATUS      DETAILS\n",
+                "    0  Income   2023-01-02         100  Cleared     Employer  [Salary]  #INV-7\n",
+            )
+        );
+        log.append(Entry::Reconcile(statement.clone())).unwrap();
+        assert_eq!(
+            reconciliation(&log, &statement),
+            "Statement:  100 on 2023-01-02\nCleared:    100\nDifference: 0\n"
+        );
+    }
+
+    #[test]
+    fn test_variance() {
+        let mut log = log();
+        let plan = |value, date: &str, planned, fulfils| Transaction {
+            category: "Food".to_owned(),
+            planned,
+            fulfils,
+            ..Transaction::new(value, TransactionKind::Expense, date.parse().unwrap())
+        };
+        log.append(Entry::Create(plan(40, "2023-01-02", true, None)))
+            .unwrap();
+        log.append(Entry::Create(plan(35, "2023-01-03", false, Some(2))))
+            .unwrap();
+        assert_eq!(
+            transactions_list(&log.transaction_records()[2..]),
+            concat!(
+                "   ID  KIND     DATE             VALUE  STATUS      DETAILS\n",
+                "    2  Expense  2023-01-02          40  Planned     [Food]  fulfilled by 3\n",
+                "    3  Expense  2023-01-03          35  -           [Food]  fulfils plan 2\n",
+            )
+        );
+        assert_eq!(
+            variance(&log.variance(&log.timeline_data_by(&log.date_range(), Granularity::Month))),
+            concat!(
+                "PERIOD                  CATEGORY                 PLANNED      ACTUAL    VARIANCE\n",
+                "2023-01-01..2023-01-03  -                              0         -30         -30\n",
+                "2023-01-01..2023-01-03  Food                         -40         -35           5\n",
+                "2023-01-01..2023-01-03  Salary                         0         100         100\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_timeline() {
+        assert_eq!(
+            timeline(&log().timeline_data()),
+            concat!(
+                "DATE            INCOME    EXPENSES     BALANCE\n",
+                "2023-01-01           0           0           0\n",
+                "2023-01-02         100           0         100\n",
+                "2023-01-03           0          30          70\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_timeline_with_interest() {
+        let mut log = log();
+        assert_eq!(interest(log.interest()), "No interest.\n");
+        log.append(Entry::SetInterest(Some(Interest {
+            savings_rate: 365.0,
+            credit_rate: 0.0,
+            basis: RateBasis::Apr,
+            compounding: Compounding::Daily,
+        })))
+        .unwrap();
+        assert_eq!(
+            interest(log.interest()),
+            "Savings:     365% APR\nOverdrawn:   0% APR\nCompounding: Daily\n"
+        );
+        assert_eq!(
+            timeline(&log.timeline_data()),
+            concat!(
+                "DATE            INCOME    EXPENSES     BALANCE    INTEREST\n",
+                "2023-01-01           0           0           0           0\n",
+                "2023-01-02         100           0         101           1\n",
+                "2023-01-03           0          30          72           1\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_inflation() {
+        let mut log = Log::default();
+        assert_eq!(inflation(log.inflation()), "No inflation.\n");
+        log.append(Entry::SetInflation(Some(Inflation {
+            rate: 2.5,
+            by_year: [(2024, 10.0)].into(),
+        })))
+        .unwrap();
+        assert_eq!(
+            inflation(log.inflation()),
+            "Inflation: 2.5% a year\n      2024: 10%\n"
+        );
+        log.append(Entry::Schedule(Schedule {
+            transaction: Transaction::new(
+                1000,
+                TransactionKind::Expense,
+                "2023-01-01".parse().unwrap(),
+            ),
+            frequency: Frequency::Yearly,
+            until: None,
+            indexed: true,
+        }))
+        .unwrap();
+        assert_eq!(
+            schedules_list(&log.schedule_records()),
+            concat!(
+                "   ID  KIND     FROM             VALUE  EVERY        UNTIL     \n",
+                "    0  Expense  2023-01-01        1000  Yearly       -  grows with inflation\n",
+            )
+        );
+        let years =
+            DateRange::new("2023-01-01".parse().unwrap(), "2025-12-31".parse().unwrap()).unwrap();
+        assert_eq!(
+            timeline(&log.forecast_data_by(&years, Granularity::Year, 3)),
+            concat!(
+                "DATE                        INCOME    EXPENSES     BALANCE\n",
+                "2023-01-01..2023-12-31           0        1000       -1000  forecast\n",
+                "2024-01-01..2024-12-31           0        1025       -2025  forecast\n",
+                "2025-01-01..2025-12-31           0        1128       -3153  forecast\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_timeline_by_week() {
+        let log = log();
+        assert_eq!(
+            timeline(&log.timeline_data_by(&log.date_range(), Granularity::Week(Weekday::Mon))),
+            concat!(
+                "DATE                        INCOME    EXPENSES     BALANCE\n",
+                "2023-01-01                       0           0           0\n",
+                "2023-01-02..2023-01-03         100          30          70\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_forecast() {
+        let mut log = log();
+        log.append(Entry::Schedule(Schedule {
+            transaction: Transaction::new(
+                10,
+                TransactionKind::Expense,
+                "2023-01-04".parse().unwrap(),
+            ),
+            frequency: Frequency::Weekly,
+            until: None,
+            indexed: false,
+        }))
+        .unwrap();
+        log.append(Entry::SetDate(
+            DateRange::new("2023-01-02".parse().unwrap(), "2023-01-05".parse().unwrap()).unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(
+            schedules_list(&log.schedule_records()),
+            concat!(
+                "   ID  KIND     FROM             VALUE  EVERY        UNTIL     \n",
+                "    0  Expense  2023-01-04          10  Weekly       -\n",
+            )
+        );
+        // the three days to 2023-01-03 spread the unscheduled history
+        // of 100 income and 30 expenses over the following days
+        assert_eq!(
+            timeline(&log.forecast_data_by(&log.date_range(), Granularity::Day, 3)),
+            concat!(
+                "DATE            INCOME    EXPENSES     BALANCE\n",
+                "2023-01-02         100           0         100\n",
+                "2023-01-03           0          30          70\n",
+                "2023-01-04          33          20          83  forecast\n",
+                "2023-01-05          33          10         106  forecast\n",
+            )
+        );
+        assert_eq!(
+            baseline(&log.baseline(3).unwrap()),
+            concat!(
+                "Baseline over 2023-01-01..2023-01-03\n",
+                "CATEGORY                INCOME/DAY  EXPENSES/DAY\n",
+                "-                             0.00         10.00\n",
+                "Salary                       33.33          0.00\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_scenarios() {
+        let mut log = log();
+        let branch = |name: &str, entry| Entry::Branch(name.to_owned(), Box::new(entry));
+        log.append(Entry::Fork("part-time".to_owned())).unwrap();
+        log.append(Entry::Fork("no lunch".to_owned())).unwrap();
+        log.append(branch("no lunch", Entry::Delete(1))).unwrap();
+        log.append(branch(
+            "part-time",
+            Entry::Create(Transaction {
+                payee: "Employer".to_owned(),
+                category: "Salary".to_owned(),
+                ..Transaction::new(50, TransactionKind::Income, "2023-01-02".parse().unwrap())
+            }),
+        ))
+        .unwrap();
+        log.append(branch("part-time", Entry::Delete(0))).unwrap();
+        assert_eq!(
+            scenarios(&log),
+            concat!(
+                "SCENARIO              CHANGES     BALANCE  DIFFERENCE\n",
+                "no lunch                    1         100          30\n",
+                "part-time                   2          20         -50\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_loan_schedule() {
+        let loan = Loan {
+            name: "Bank".to_owned(),
+            principal: 300,
+            annual_rate: 12.0,
+            term_months: 6,
+            first_payment: "2023-01-15".parse().unwrap(),
+            extra: 20,
+            lump_sums: vec![],
+        };
+        assert_eq!(
+            loan_schedule(
+                &loan,
+                loan.regular_payment().unwrap(),
+                &loan.schedule().unwrap()
+            ),
+            concat!(
+                "    #  DATE         PRINCIPAL    INTEREST     BALANCE\n",
+                "    1  2023-01-15          69           3         231\n",
+                "    2  2023-02-15          70           2         161\n",
+                "    3  2023-03-15          70           2          91\n",
+                "    4  2023-04-15          71           1          20\n",
+                "    5  2023-05-15          20           0           0\n",
+                "Payment:  52 plus 20\n",
+                "Payoff:   2023-05-15\n",
+                "Interest: 8\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let log = log();
+        assert_eq!(
+            summary(&log.date_range(), &log.timeline_data(), 0),
+            concat!(
+                "Range:    2023-01-01 to 2023-01-03\n",
+                "Income:   100\n",
+                "Expenses: 30\n",
+                "Net:      70\n",
+                "Balance:  70\n",
+                "Lowest:   0 on 2023-01-01\n",
+            )
+        );
+        let date_range =
+            DateRange::new("2023-01-02".parse().unwrap(), "2023-01-03".parse().unwrap()).unwrap();
+        assert!(summary(&date_range, &log.timeline_data_in(&date_range), 80)
+            .ends_with("Lowest:   70 on 2023-01-03\nWarning:  below 80 from 2023-01-03\n"));
+
+        // a negative threshold, such as the floor of a credit line, is
+        // warned about past the overdraft
+        let mut log = log;
+        log.append(Entry::Create(Transaction::new(
+            200,
+            TransactionKind::Expense,
+            "2023-01-03".parse().unwrap(),
+        )))
+        .unwrap();
+        assert!(
+            summary(&log.date_range(), &log.timeline_data(), -100).ends_with(concat!(
+                "Warning:  overdrawn from 2023-01-03\n",
+                "Warning:  below -100 from 2023-01-03\n",
+            ))
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use chrono::Duration;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use financial_planner_core::*;
+
+/// a log of `n` transactions spread evenly over ten years, with every
+/// tenth transaction later deleted
+fn ten_year_log(n: usize) -> Log {
+    let start: Date = "2020-01-01".parse().unwrap();
+    let end = start + Duration::days(3652);
+    let mut log = Log::from(Entry::SetDate(DateRange::new(start, end).unwrap()));
+    for i in 0..n {
+        log.append(Entry::Create(Transaction::new(
+            (i % 500) as Dollars,
+            if i % 3 == 0 {
+                TransactionKind::Income
+            } else {
+                TransactionKind::Expense
+            },
+            start + Duration::days((i % 3653) as i64),
+        )))
+        .unwrap();
+        if i % 10 == 9 {
+            log.append(Entry::Delete(i as TransactionId)).unwrap();
+        }
+    }
+    log
+}
+
+fn timeline_data(c: &mut Criterion) {
+    let log = ten_year_log(50_000);
+    c.bench_function("timeline_data 10 years 50k transactions", |b| {
+        b.iter(|| black_box(&log).timeline_data())
+    });
+    c.bench_function("transaction_records 50k transactions", |b| {
+        b.iter(|| black_box(&log).transaction_records())
+    });
+    c.bench_function("clone and append to 50k transactions", |b| {
+        b.iter(|| {
+            let mut log = black_box(&log).clone();
+            log.append(Entry::Delete(0)).unwrap();
+            log
+        })
+    });
+}
+
+criterion_group!(benches, timeline_data);
+criterion_main!(benches);
@@ -0,0 +1,1141 @@
+use crate::date_range::DateRange;
+use crate::forecast::{Baseline, Schedule, ScheduleId, ScheduleRecord, Trend};
+use crate::granularity::Granularity;
+use crate::inflation::Inflation;
+use crate::interest::{Accrual, Interest};
+use crate::reconcile::{Reconciliation, TransactionStatus};
+use crate::rules::{Rule, RuleId, RuleRecord};
+use imbl::{OrdMap, Vector};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "yew")]
+use std::rc::Rc;
+#[cfg(feature = "yew")]
+use yew::Reducible;
+
+pub type Date = chrono::NaiveDate;
+pub type Dollars = i32;
+pub type TransactionId = u16;
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum TransactionKind {
+    Income,
+    Expense,
+}
+
+impl std::fmt::Display for TransactionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TransactionKind::*;
+        match self {
+            Income => write!(f, "Income"),
+            Expense => write!(f, "Expense"),
+        }
+    }
+}
+
+// Compound types
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub value: Dollars,
+    pub kind: TransactionKind,
+    pub date: Date,
+    /// who was paid, or who paid
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub payee: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub memo: String,
+    /// a cheque or invoice number, say
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// what the money was for, e.g. "Groceries"; empty if uncategorized
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub category: String,
+    /// an estimate of something expected, rather than something that has
+    /// happened
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub planned: bool,
+    /// the planned transaction this actual one fulfils, which then no
+    /// longer counts toward the balance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fulfils: Option<TransactionId>,
+}
+
+impl Transaction {
+    /// a transaction with no details beyond its value, kind and date
+    pub fn new(value: Dollars, kind: TransactionKind, date: Date) -> Self {
+        Self {
+            value,
+            kind,
+            date,
+            payee: String::new(),
+            memo: String::new(),
+            reference: None,
+            category: String::new(),
+            planned: false,
+            fulfils: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TransactionRecord {
+    pub transaction: Transaction,
+    pub id: TransactionId,
+    pub status: TransactionStatus,
+    /// the actual transaction that fulfils this one, if it is planned
+    pub fulfilled_by: Option<TransactionId>,
+}
+
+impl From<(TransactionId, Transaction)> for TransactionRecord {
+    fn from(value: (TransactionId, Transaction)) -> Self {
+        Self {
+            transaction: value.1,
+            id: value.0,
+            status: TransactionStatus::default(),
+            fulfilled_by: None,
+        }
+    }
+}
+
+/// Totals of the transactions dated within `period`, and the balance at
+/// the end of it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DateSummary {
+    pub period: DateRange,
+    pub income: Dollars,
+    pub expenses: Dollars,
+    pub balance: Dollars,
+    /// interest paid into the balance within `period`, or charged if
+    /// negative; it is not counted in `income` or `expenses`
+    pub interest: Dollars,
+    /// whether any of `period` lies past the last recorded transaction,
+    /// so that its totals are projected rather than known
+    pub forecast: bool,
+}
+
+impl Default for DateSummary {
+    fn default() -> Self {
+        Self {
+            period: Default::default(),
+            income: 0,
+            expenses: 0,
+            balance: 0,
+            interest: 0,
+            forecast: false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TimelineData(Vec<DateSummary>);
+
+impl TimelineData {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = &'a DateSummary> {
+        self.0.iter()
+    }
+    /// returns the date of the earliest summary. Returns `None` if
+    /// list is empty.
+    pub fn start_date(&self) -> Option<Date> {
+        // if list is known to be sorted and non-empty, this is more efficient:
+        // `Some(self.0[0].period.start())`
+        self.iter().map(|s| s.period.start()).min()
+    }
+}
+
+impl IntoIterator for TimelineData {
+    type IntoIter = <Vec<DateSummary> as IntoIterator>::IntoIter;
+    type Item = <Vec<DateSummary> as IntoIterator>::Item;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromIterator<DateSummary> for TimelineData {
+    fn from_iter<I: IntoIterator<Item = DateSummary>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Default for TimelineData {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TransactionsListData {
+    pub transaction_records: Vec<TransactionRecord>,
+}
+
+impl Default for TransactionsListData {
+    fn default() -> Self {
+        Self {
+            transaction_records: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum Entry {
+    Create(Transaction),
+    Delete(TransactionId),
+    /// older logs may hold an inverted range, which is read as its reverse
+    #[serde(deserialize_with = "DateRange::deserialize_legacy")]
+    SetDate(DateRange),
+    Schedule(Schedule),
+    Unschedule(ScheduleId),
+    AddRule(Rule),
+    RemoveRule(RuleId),
+    /// tick a transaction off against a statement, or untick it
+    SetCleared(TransactionId, bool),
+    Reconcile(Reconciliation),
+    /// start a named what-if scenario branched off the log
+    Fork(String),
+    /// an entry that only applies within the named scenario
+    Branch(String, Box<Entry>),
+    DropScenario(String),
+    /// the interest the balance earns or is charged, or none
+    SetInterest(Option<Interest>),
+    /// the inflation indexed schedules grow with, or none
+    SetInflation(Option<Inflation>),
+}
+
+/// Income and expenses of every live transaction dated on one day.
+/// Summed as `i64` so that deleting a transaction can be undone exactly;
+/// values are saturated to `Dollars` only when a summary is produced.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct DayTotals {
+    income: i64,
+    expenses: i64,
+    /// number of transactions counted; days without any are removed
+    count: i64,
+}
+
+impl DayTotals {
+    fn apply(&mut self, t: &Transaction, sign: i64) {
+        match t.kind {
+            TransactionKind::Income => self.income += sign * t.value as i64,
+            TransactionKind::Expense => self.expenses += sign * t.value as i64,
+        }
+        self.count += sign;
+    }
+}
+
+fn saturate(value: i64) -> Dollars {
+    value.clamp(Dollars::MIN as i64, Dollars::MAX as i64) as Dollars
+}
+
+/// How many transactions, schedules and rules a scenario has added. Their
+/// ids count down from the top, so that they stay the same as the real log
+/// grows and entries branched off it can refer to them.
+#[derive(Debug, Clone, Copy, Default)]
+struct Hypothetical {
+    created: u32,
+    scheduled: u32,
+    ruled: u32,
+}
+
+/// the next id counted up by `count`, or for a scenario's addition,
+/// counted down from the top by `hypothetical`. There is none once the
+/// ids counted down would meet those counted up.
+fn next_id(count: &mut u32, hypothetical: Option<&mut u32>) -> Option<u16> {
+    match hypothetical {
+        Some(n) => {
+            let id = u32::from(u16::MAX)
+                .checked_sub(*n)
+                .filter(|id| *id >= *count)?;
+            *n += 1;
+            u16::try_from(id).ok()
+        }
+        None => {
+            let id = u16::try_from(*count).ok()?;
+            *count += 1;
+            Some(id)
+        }
+    }
+}
+
+/// Why an entry could not be appended to a `Log`; the log is left as it
+/// was.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AppendError {
+    /// there is no id left for another transaction, schedule or rule,
+    /// named by the field, where a scenario's ids meet the log's
+    IdsExhausted(&'static str),
+    /// an interest or inflation rate is out of range or not a number
+    InvalidRate(f64),
+}
+
+impl std::fmt::Display for AppendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use AppendError::*;
+        match self {
+            IdsExhausted(kind) => write!(f, "there are no {kind} ids left"),
+            InvalidRate(rate) => write!(f, "{rate} is not a valid rate"),
+        }
+    }
+}
+
+impl std::error::Error for AppendError {}
+
+/// reject an entry, or one branched into a scenario, setting a rate the
+/// projections can't be computed with
+fn check_rates(e: &Entry) -> Result<(), AppendError> {
+    let rate = match e {
+        Entry::SetInterest(Some(interest)) => interest.invalid_rate(),
+        Entry::SetInflation(Some(inflation)) => inflation.invalid_rate(),
+        Entry::Branch(_, entry) => return check_rates(entry),
+        _ => None,
+    };
+    rate.map_or(Ok(()), |rate| Err(AppendError::InvalidRate(rate)))
+}
+
+/// The append-only list of entries together with the projections
+/// computed from it. The projections are updated as each entry is
+/// appended, and every field is a persistent collection, so cloning a
+/// `Log` and appending to the clone is O(log n) and shares structure
+/// with the original.
+#[derive(Clone, Default)]
+pub struct Log {
+    entries: Vector<Entry>,
+    /// number of `Create` entries, i.e. the id of the next transaction
+    created: u32,
+    transactions: OrdMap<TransactionId, Transaction>,
+    daily_totals: OrdMap<Date, DayTotals>,
+    date_range: Option<DateRange>,
+    /// number of `Schedule` entries, i.e. the id of the next schedule
+    scheduled: u32,
+    schedules: OrdMap<ScheduleId, Schedule>,
+    /// number of `AddRule` entries, i.e. the id of the next rule
+    ruled: u32,
+    rules: OrdMap<RuleId, Rule>,
+    /// the status of every transaction that isn't `Uncleared`
+    statuses: OrdMap<TransactionId, TransactionStatus>,
+    reconciliations: Vector<Reconciliation>,
+    /// the actual transaction fulfilling each fulfilled plan; a fulfilled
+    /// plan is left out of `daily_totals`
+    fulfilled_by: OrdMap<TransactionId, TransactionId>,
+    /// the hypothetical entries of each scenario, which the projections
+    /// above ignore
+    scenarios: OrdMap<String, Vector<Entry>>,
+    /// what a scenario has added, in a scenario's log only
+    hypothetical: Option<Hypothetical>,
+    interest: Option<Interest>,
+    inflation: Option<Inflation>,
+}
+
+impl std::fmt::Debug for Log {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Log")
+            .field("entries", &self.entries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Log {
+    fn eq(&self, other: &Self) -> bool {
+        // everything else is derived from the entries
+        self.entries == other.entries
+    }
+}
+
+#[cfg(feature = "yew")]
+impl Reducible for Log {
+    type Action = Entry;
+    fn reduce(self: Rc<Self>, event: Entry) -> Rc<Self> {
+        let mut log = (*self).clone();
+        // an entry that cannot be appended leaves the log as it was
+        match log.append(event) {
+            Ok(()) => log.into(),
+            Err(_) => self,
+        }
+    }
+}
+
+impl Log {
+    pub fn entries(&self) -> Vec<Entry> {
+        self.entries.iter().cloned().collect_vec()
+    }
+
+    pub fn append(&mut self, e: Entry) -> Result<(), AppendError> {
+        check_rates(&e)?;
+        match &e {
+            Entry::Create(t) => {
+                let id = next_id(
+                    &mut self.created,
+                    self.hypothetical.as_mut().map(|h| &mut h.created),
+                )
+                .ok_or(AppendError::IdsExhausted("transaction"))?;
+                if let Some(plan) = t
+                    .fulfils
+                    .filter(|plan| !t.planned && self.is_open_plan(*plan))
+                {
+                    // the actual transaction takes the plan's place in the
+                    // totals
+                    let plan_transaction = self.transactions[&plan].clone();
+                    self.tally(&plan_transaction, -1);
+                    self.fulfilled_by.insert(plan, id);
+                }
+                self.tally(t, 1);
+                self.transactions.insert(id, t.clone());
+            }
+            // reconciled transactions are locked
+            Entry::Delete(id) if self.status(*id) == TransactionStatus::Reconciled => {}
+            Entry::Delete(id) => {
+                self.statuses.remove(id);
+                if let Some(t) = self.transactions.remove(id) {
+                    // a fulfilled plan is already out of the totals
+                    if self.fulfilled_by.remove(id).is_none() {
+                        self.tally(&t, -1);
+                    }
+                    // deleting the actual transaction reopens its plan
+                    if let Some(plan) = t
+                        .fulfils
+                        .filter(|plan| self.fulfilled_by.get(plan) == Some(id))
+                    {
+                        self.fulfilled_by.remove(&plan);
+                        let plan_transaction = self.transactions[&plan].clone();
+                        self.tally(&plan_transaction, 1);
+                    }
+                }
+            }
+            Entry::SetDate(date_range) => self.date_range = Some(date_range.clone()),
+            Entry::Schedule(s) => {
+                let id = next_id(
+                    &mut self.scheduled,
+                    self.hypothetical.as_mut().map(|h| &mut h.scheduled),
+                )
+                .ok_or(AppendError::IdsExhausted("schedule"))?;
+                self.schedules.insert(id, s.clone());
+            }
+            Entry::Unschedule(id) => {
+                self.schedules.remove(id);
+            }
+            Entry::AddRule(r) => {
+                let id = next_id(
+                    &mut self.ruled,
+                    self.hypothetical.as_mut().map(|h| &mut h.ruled),
+                )
+                .ok_or(AppendError::IdsExhausted("rule"))?;
+                self.rules.insert(id, r.clone());
+            }
+            Entry::RemoveRule(id) => {
+                self.rules.remove(id);
+            }
+            Entry::SetCleared(id, cleared) => match self.status(*id) {
+                TransactionStatus::Reconciled => {}
+                // plans aren't on any statement
+                _ if self.transactions.get(id).is_none_or(|t| t.planned) => {}
+                _ if *cleared => {
+                    self.statuses.insert(*id, TransactionStatus::Cleared);
+                }
+                _ => {
+                    self.statuses.remove(id);
+                }
+            },
+            Entry::Reconcile(r) => {
+                let cleared = self
+                    .statuses
+                    .iter()
+                    .filter(|(id, status)| {
+                        **status == TransactionStatus::Cleared
+                            && self.transactions[id].date <= r.statement_date
+                    })
+                    .map(|(id, _)| *id)
+                    .collect_vec();
+                for id in cleared {
+                    self.statuses.insert(id, TransactionStatus::Reconciled);
+                }
+                self.reconciliations.push_back(r.clone());
+            }
+            Entry::Fork(name) => {
+                if !self.scenarios.contains_key(name) {
+                    self.scenarios.insert(name.clone(), Vector::new());
+                }
+            }
+            Entry::Branch(name, entry) => {
+                if let Some(entries) = self.scenarios.get_mut(name) {
+                    entries.push_back((**entry).clone());
+                }
+            }
+            Entry::DropScenario(name) => {
+                self.scenarios.remove(name);
+            }
+            Entry::SetInterest(interest) => self.interest = *interest,
+            Entry::SetInflation(inflation) => self.inflation = inflation.clone(),
+        }
+        self.entries.push_back(e);
+        Ok(())
+    }
+
+    /// add `t` to its day's totals, or with a `sign` of -1 take it away
+    fn tally(&mut self, t: &Transaction, sign: i64) {
+        let mut totals = self.daily_totals.get(&t.date).copied().unwrap_or_default();
+        totals.apply(t, sign);
+        if totals.count == 0 {
+            self.daily_totals.remove(&t.date);
+        } else {
+            self.daily_totals.insert(t.date, totals);
+        }
+    }
+
+    /// whether `id` is a live planned transaction not yet fulfilled
+    pub fn is_open_plan(&self, id: TransactionId) -> bool {
+        self.transactions.get(&id).is_some_and(|t| t.planned)
+            && !self.fulfilled_by.contains_key(&id)
+    }
+
+    pub fn transaction_records(&self) -> Vec<TransactionRecord> {
+        self.transactions
+            .iter()
+            .map(|(id, t)| TransactionRecord {
+                transaction: t.clone(),
+                id: *id,
+                status: self.status(*id),
+                fulfilled_by: self.fulfilled_by.get(id).copied(),
+            })
+            .collect_vec()
+    }
+
+    pub fn status(&self, id: TransactionId) -> TransactionStatus {
+        self.statuses.get(&id).copied().unwrap_or_default()
+    }
+
+    /// every live transaction by id, without cloning them
+    pub(crate) fn transactions(&self) -> impl Iterator<Item = (&TransactionId, &Transaction)> {
+        self.transactions.iter()
+    }
+
+    /// the live transactions that are cleared or reconciled
+    pub(crate) fn cleared_transactions(&self) -> impl Iterator<Item = &Transaction> {
+        self.statuses
+            .keys()
+            .filter_map(|id| self.transactions.get(id))
+    }
+
+    /// the statements reconciled so far, oldest first
+    pub fn reconciliations(&self) -> Vec<Reconciliation> {
+        self.reconciliations.iter().cloned().collect_vec()
+    }
+
+    /// the interest most recently set, if any
+    pub fn interest(&self) -> Option<Interest> {
+        self.interest
+    }
+
+    /// the inflation most recently set, if any
+    pub fn inflation(&self) -> Option<&Inflation> {
+        self.inflation.as_ref()
+    }
+
+    /// a copy of the log to append a scenario's entries to, in which
+    /// transactions are given ids apart from the real log's
+    pub(crate) fn branch_off(&self) -> Log {
+        Log {
+            hypothetical: Some(Hypothetical::default()),
+            ..self.clone()
+        }
+    }
+
+    /// the names of the what-if scenarios, in order
+    pub fn scenario_names(&self) -> Vec<String> {
+        self.scenarios.keys().cloned().collect_vec()
+    }
+
+    /// the hypothetical entries of the named scenario, oldest first
+    pub fn scenario_entries(&self, name: &str) -> Option<Vec<Entry>> {
+        self.scenarios
+            .get(name)
+            .map(|entries| entries.iter().cloned().collect_vec())
+    }
+
+    pub fn schedule_records(&self) -> Vec<ScheduleRecord> {
+        self.schedules
+            .iter()
+            .map(|(id, s)| ScheduleRecord {
+                schedule: s.clone(),
+                id: *id,
+            })
+            .collect_vec()
+    }
+
+    pub fn rule_records(&self) -> Vec<RuleRecord> {
+        self.rules
+            .iter()
+            .map(|(id, r)| RuleRecord {
+                rule: r.clone(),
+                id: *id,
+            })
+            .collect_vec()
+    }
+
+    /// the date of the latest live transaction, after which the
+    /// balance can only be forecast
+    pub fn last_transaction_date(&self) -> Option<Date> {
+        self.daily_totals.get_max().map(|(date, _)| *date)
+    }
+
+    pub fn create_entries(&self) -> Vec<(usize, TransactionRecord)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| match e {
+                Entry::Create(t) => Some((i, (i as TransactionId, (*t).clone()).into())),
+                _ => None,
+            })
+            .collect_vec()
+    }
+
+    pub fn delete_entries(&self) -> Vec<(usize, TransactionId)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| match e {
+                Entry::Delete(id) => Some((i, *id)),
+                _ => None,
+            })
+            .collect_vec()
+    }
+
+    pub fn transaction_id_at(&self, index: usize) -> TransactionId {
+        self.entries
+            .iter()
+            .filter(|e| match e {
+                Entry::Create(_) => true,
+                _ => false,
+            })
+            .count() as TransactionId
+    }
+
+    pub fn latest_create(&self, id: &TransactionId) -> Option<usize> {
+        self.create_entries()
+            .iter()
+            .find_map(|(i, tr)| if tr.id == *id { Some(*i) } else { None })
+    }
+
+    pub fn date_range(&self) -> DateRange {
+        self.date_range.clone().unwrap_or_else(|| {
+            DateRange::new("2023-01-01".parse().unwrap(), "2023-01-31".parse().unwrap()).unwrap()
+        })
+    }
+
+    pub fn timeline_data(&self) -> TimelineData {
+        self.timeline_data_in(&self.date_range())
+    }
+
+    /// like `timeline_data`, but over `date_range` instead of the
+    /// range most recently set in the log
+    pub fn timeline_data_in(&self, date_range: &DateRange) -> TimelineData {
+        self.timeline_data_by(date_range, Granularity::Day)
+    }
+
+    /// one summary per `granularity` period of `date_range`
+    pub fn timeline_data_by(
+        &self,
+        date_range: &DateRange,
+        granularity: Granularity,
+    ) -> TimelineData {
+        summarize(
+            &self.daily_totals,
+            date_range,
+            granularity,
+            0,
+            None,
+            self.interest,
+        )
+    }
+
+    /// recorded income and expenses of each category over the
+    /// `trailing_days` up to the last transaction, less what the
+    /// schedules account for
+    pub fn baseline(&self, trailing_days: u32) -> Option<Baseline> {
+        let end = self.last_transaction_date()?;
+        let start =
+            end.checked_sub_days(chrono::Days::new(trailing_days.saturating_sub(1) as u64))?;
+        let window = DateRange::new(start, end).ok()?;
+        let mut categories = BTreeMap::<String, Trend>::new();
+        // as counted in `daily_totals`
+        for (id, t) in &self.transactions {
+            if window.contains(&t.date) && !self.fulfilled_by.contains_key(id) {
+                categories
+                    .entry(t.category.clone())
+                    .or_default()
+                    .apply(t, 1);
+            }
+        }
+        for schedule in self.schedules.values() {
+            for date in schedule.occurrences_in(&window) {
+                let t = schedule.on(date, self.inflation());
+                categories
+                    .entry(t.category.clone())
+                    .or_default()
+                    .apply(&t, -1);
+            }
+        }
+        for trend in categories.values_mut() {
+            trend.income = trend.income.max(0);
+            trend.expenses = trend.expenses.max(0);
+        }
+        categories.retain(|_, trend| *trend != Trend::default());
+        Some(Baseline { window, categories })
+    }
+
+    /// like `timeline_data_by`, but with the days after the last
+    /// transaction filled in from the schedules and the baseline over
+    /// `trailing_days`. The balance starts from what was recorded before
+    /// `date_range`, so that it ends on what the account is forecast to
+    /// hold.
+    pub fn forecast_data_by(
+        &self,
+        date_range: &DateRange,
+        granularity: Granularity,
+        trailing_days: u32,
+    ) -> TimelineData {
+        let opening = self
+            .daily_totals
+            .range(..date_range.start())
+            .fold(0i64, |balance, (_, totals)| {
+                balance.saturating_add(totals.income - totals.expenses)
+            });
+        let baseline = self.baseline(trailing_days);
+        let forecast_start = match self.last_transaction_date() {
+            Some(last) => match last.succ_opt() {
+                Some(date) => date.max(date_range.start()),
+                None => {
+                    return summarize(
+                        &self.daily_totals,
+                        date_range,
+                        granularity,
+                        opening,
+                        None,
+                        self.interest,
+                    )
+                }
+            },
+            None => date_range.start(),
+        };
+        let mut daily_totals = self.daily_totals.clone();
+        if let Ok(forecast_range) = DateRange::new(forecast_start, date_range.end()) {
+            if let Some(baseline) = &baseline {
+                for date in forecast_range.days() {
+                    let (income, expenses) =
+                        baseline.on_day((date - baseline.window.end()).num_days());
+                    let totals = daily_totals.entry(date).or_default();
+                    totals.income += income;
+                    totals.expenses += expenses;
+                }
+            }
+            for schedule in self.schedules.values() {
+                for date in schedule.occurrences_in(&forecast_range) {
+                    daily_totals
+                        .entry(date)
+                        .or_default()
+                        .apply(&schedule.on(date, self.inflation()), 1);
+                }
+            }
+        }
+        summarize(
+            &daily_totals,
+            date_range,
+            granularity,
+            opening,
+            Some(forecast_start),
+            self.interest,
+        )
+    }
+}
+
+/// one summary per `granularity` period of `date_range`, with the balance
+/// starting from `opening`, marking those that reach `forecast_start` as
+/// forecast. With `interest`, the balance accrues it day by day.
+fn summarize(
+    daily_totals: &OrdMap<Date, DayTotals>,
+    date_range: &DateRange,
+    granularity: Granularity,
+    opening: i64,
+    forecast_start: Option<Date>,
+    interest: Option<Interest>,
+) -> TimelineData {
+    let mut daily_totals = daily_totals
+        .range(date_range.start()..=date_range.end())
+        .peekable();
+    let mut accrual = interest.map(Accrual::new);
+    let mut balance = opening;
+    let timeline_data = granularity
+        .periods(date_range)
+        .into_iter()
+        .map(|period| {
+            let (mut income, mut expenses, mut paid) = (0i64, 0i64, 0i64);
+            match &mut accrual {
+                // without interest only the days with transactions matter
+                None => {
+                    while let Some((_, totals)) =
+                        daily_totals.next_if(|(date, _)| period.contains(date))
+                    {
+                        income += totals.income;
+                        expenses += totals.expenses;
+                    }
+                    balance += income - expenses;
+                }
+                Some(accrual) => {
+                    for date in period.days() {
+                        if let Some((_, totals)) = daily_totals.next_if(|(d, _)| **d == date) {
+                            income += totals.income;
+                            expenses += totals.expenses;
+                            balance = balance.saturating_add(totals.income - totals.expenses);
+                        }
+                        let interest = accrual.day(balance, date);
+                        paid = paid.saturating_add(interest);
+                        balance = balance.saturating_add(interest);
+                    }
+                }
+            }
+            DateSummary {
+                forecast: forecast_start.is_some_and(|start| start <= period.end()),
+                period,
+                income: saturate(income),
+                expenses: saturate(expenses),
+                balance: saturate(balance),
+                interest: saturate(paid),
+            }
+        })
+        .collect_vec();
+    TimelineData(timeline_data)
+}
+
+impl From<Entry> for Log {
+    fn from(value: Entry) -> Self {
+        Log::from(vec![value])
+    }
+}
+
+/// Replays `value`, leaving out any entry that cannot be appended, as it
+/// could not have been when the log was written.
+impl From<Vec<Entry>> for Log {
+    fn from(value: Vec<Entry>) -> Self {
+        let mut log = Log::default();
+        for entry in value {
+            let _ = log.append(entry);
+        }
+        log
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::marker::PhantomData;
+
+    use super::{
+        Date, DateRange, DateSummary, Dollars, Entry, Granularity, Log, Transaction, TransactionId,
+        TransactionKind, TransactionRecord,
+    };
+    use chrono::NaiveDate;
+    use itertools::Itertools;
+    use quickcheck::Arbitrary;
+
+    fn arbitrary_range<T: Clone>(
+        g: &mut quickcheck::Gen,
+        range: impl Iterator<Item = T>,
+    ) -> Option<T> {
+        g.choose((range.collect_vec()).as_slice())
+            .map(|t| (*t).clone())
+    }
+
+    #[derive(Clone)]
+    struct DateWrapper(Date);
+
+    impl DateWrapper {
+        pub fn into_inner(self) -> Date {
+            self.0
+        }
+    }
+
+    impl Arbitrary for DateWrapper {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            loop {
+                let date = NaiveDate::from_ymd_opt(
+                    arbitrary_range(g, 2020..2025).unwrap(),
+                    arbitrary_range(g, 1_u32..13).unwrap(),
+                    arbitrary_range(g, 1_u32..32).unwrap(),
+                );
+                if date.is_some() {
+                    return Self(date.unwrap());
+                }
+            }
+        }
+    }
+
+    impl Arbitrary for Transaction {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            Transaction::new(
+                Dollars::arbitrary(g),
+                *g.choose(&[TransactionKind::Income, TransactionKind::Expense])
+                    .unwrap(),
+                DateWrapper::arbitrary(g).0,
+            )
+        }
+    }
+
+    impl Arbitrary for TransactionRecord {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            TransactionRecord {
+                transaction: Transaction::arbitrary(g),
+                id: arbitrary_range(g, 1..50).unwrap(), // limit id range to get overlaps
+                status: Default::default(),
+                fulfilled_by: None,
+            }
+        }
+    }
+
+    impl Arbitrary for Entry {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            match g.choose(&[1, 2, 3]).unwrap() {
+                1 => Self::Create(Transaction::arbitrary(g)),
+                2 => Self::Delete(TransactionId::arbitrary(g)),
+                3 => Self::SetDate(DateRange::arbitrary(g)),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    impl Arbitrary for DateRange {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            let a = DateWrapper::arbitrary(g).into_inner();
+            let b = DateWrapper::arbitrary(g).into_inner();
+            DateRange::new(a.min(b), a.max(b)).unwrap()
+        }
+    }
+
+    trait Predicate {}
+    impl Predicate for () {}
+    #[derive(Debug, PartialEq, Clone)]
+    struct NonEmpty;
+    impl Predicate for NonEmpty {}
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct PredicatedLog<P: Predicate> {
+        log: Log,
+        _phantom_data: PhantomData<P>,
+    }
+
+    impl<P: Predicate> PredicatedLog<P> {
+        fn into_inner(self) -> Log {
+            self.log
+        }
+    }
+
+    impl Arbitrary for PredicatedLog<NonEmpty> {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            let mut entries = Vec::<Entry>::arbitrary(g);
+            while entries.len() == 0 {
+                entries = Vec::<Entry>::arbitrary(g)
+            }
+            Self {
+                log: Log::from(entries),
+                _phantom_data: PhantomData,
+            }
+        }
+    }
+
+    impl Arbitrary for PredicatedLog<()> {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            Self {
+                log: Log::from(Vec::<Entry>::arbitrary(g)),
+                _phantom_data: PhantomData,
+            }
+        }
+    }
+
+    // 1. For every non-deleted transaction, there exists exactly one
+    //    date summary whose date is equal to the transaction date
+    #[quickcheck]
+    fn test_timeline_data_1(log: PredicatedLog<NonEmpty>) -> bool {
+        let log = log.into_inner();
+        let summaries = log.timeline_data();
+        let transaction_records = log.transaction_records();
+        for tr in transaction_records {
+            if summaries
+                .iter()
+                .any(|s| s.period.contains(&tr.transaction.date))
+            {
+                continue;
+            } else {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    // compute transactions list
+    // When  the transactions list is empty
+    // Then  every create entry is followed by a delete entry
+    #[quickcheck]
+    fn test_transaction_records_1(log: PredicatedLog<NonEmpty>) -> bool {
+        let log = log.into_inner();
+        let transaction_records = log.transaction_records();
+        if transaction_records.is_empty() {
+            for (i, transaction) in log.create_entries() {
+                let create_id = log.transaction_id_at(i);
+                let mut j = 0;
+                for (jj, delete_id) in log.delete_entries().iter().skip(i) {
+                    if create_id == *delete_id {
+                        j = *jj;
+                        break;
+                    }
+                }
+                print!("counterexample: ({i},{j})");
+                return false;
+            }
+        }
+        true
+    }
+
+    // 2. If a transaction appears in the list, then there
+    //    is no later action that deletes that transaction
+    #[quickcheck]
+    fn test_transaction_records_2(log: PredicatedLog<NonEmpty>) -> bool {
+        let log = log.into_inner();
+        let transaction_records = log.transaction_records();
+        for transaction_record in transaction_records {
+            let id = &transaction_record.id;
+            match log.latest_create(&id) {
+                None => {
+                    println!("unspecified behavior: transaction in list but no corresponding create entry")
+                }
+                Some(create_index) => {
+                    for (delete_index, delete_id) in log.delete_entries().iter().skip(create_index)
+                    {
+                        if delete_id == id {
+                            return false;
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    // after setting the date range, the timeline data
+    // only contains dates in that range
+    #[quickcheck]
+    fn test_timeline_data_2(log: PredicatedLog<()>, date_range: DateRange) -> bool {
+        let mut log = log.into_inner();
+        log.append(Entry::SetDate(date_range.clone())).unwrap();
+        for DateSummary { period, .. } in log.timeline_data() {
+            if date_range.intersection(&period) != Some(period) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    // the timeline data agrees with replaying the whole log for
+    // every day in the range
+    #[quickcheck]
+    fn test_timeline_data_3(log: PredicatedLog<()>) -> bool {
+        let log = log.into_inner();
+        let saturate = |v: i64| v.clamp(Dollars::MIN as i64, Dollars::MAX as i64) as Dollars;
+        let mut balance = 0i64;
+        for summary in log.timeline_data() {
+            let (mut income, mut expenses) = (0i64, 0i64);
+            for tr in log.transaction_records() {
+                if !summary.period.contains(&tr.transaction.date) {
+                    continue;
+                }
+                match tr.transaction.kind {
+                    TransactionKind::Income => income += tr.transaction.value as i64,
+                    TransactionKind::Expense => expenses += tr.transaction.value as i64,
+                }
+            }
+            balance += income - expenses;
+            if (summary.income, summary.expenses, summary.balance)
+                != (saturate(income), saturate(expenses), saturate(balance))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    // appending to a clone leaves the original untouched
+    #[quickcheck]
+    fn test_append_shares_structure(log: PredicatedLog<()>, entry: Entry) -> bool {
+        let log = log.into_inner();
+        let before = (
+            log.entries(),
+            log.transaction_records(),
+            log.timeline_data(),
+        );
+        let mut appended = log.clone();
+        appended.append(entry.clone()).unwrap();
+        let mut entries = log.entries();
+        entries.push(entry);
+        (
+            log.entries(),
+            log.transaction_records(),
+            log.timeline_data(),
+        ) == before
+            && appended == Log::from(entries)
+    }
+
+    // each period ends on the same balance as its last day
+    #[quickcheck]
+    fn test_timeline_data_by(log: PredicatedLog<()>, week_start: u8) -> bool {
+        let log = log.into_inner();
+        let week_start = (0..week_start % 7).fold(chrono::Weekday::Mon, |d, _| d.succ());
+        let date_range = log.date_range();
+        let days = log.timeline_data();
+        [
+            Granularity::Week(week_start),
+            Granularity::Month,
+            Granularity::Quarter,
+            Granularity::Year,
+        ]
+        .iter()
+        .all(|granularity| {
+            let periods = log.timeline_data_by(&date_range, *granularity);
+            periods
+                .iter()
+                .map(|s| s.period.clone())
+                .eq(granularity.periods(&date_range))
+                && periods.iter().all(|s| {
+                    days.iter()
+                        .find(|d| d.period.start() == s.period.end())
+                        .map(|d| d.balance)
+                        == Some(s.balance)
+                })
+        })
+    }
+
+    // a forecast leaves the recorded history as it is
+    #[quickcheck]
+    fn test_forecast_keeps_actuals(log: PredicatedLog<()>, trailing_days: u8) -> bool {
+        let log = log.into_inner();
+        let actual = log.timeline_data();
+        let forecast =
+            log.forecast_data_by(&log.date_range(), Granularity::Day, trailing_days as u32);
+        // balances differ by what was recorded before the range
+        actual.len() == forecast.len()
+            && actual
+                .iter()
+                .zip(forecast.iter())
+                .take_while(|(_, f)| !f.forecast)
+                .all(|(a, f)| {
+                    *a == DateSummary {
+                        balance: a.balance,
+                        ..f.clone()
+                    }
+                })
+            && forecast.iter().all(|f| {
+                f.forecast
+                    == log
+                        .last_transaction_date()
+                        .is_none_or(|last| f.period.start() > last)
+            })
+    }
+}
@@ -0,0 +1,339 @@
+use crate::app_state::Date;
+use chrono::{Datelike, Days, Duration};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DateRangeError {
+    EndBeforeStart { start: Date, end: Date },
+}
+
+impl std::fmt::Display for DateRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use DateRangeError::*;
+        match self {
+            EndBeforeStart { start, end } => {
+                write!(f, "end date {end} is before start date {start}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateRangeError {}
+
+/// The days from `start` to `end`, both inclusive. A range always covers
+/// at least one day; `end < start` is rejected by every constructor,
+/// including deserialization, except for the legacy ranges of
+/// `Entry::SetDate`.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedDateRange")]
+pub struct DateRange {
+    start: Date,
+    end: Date,
+}
+
+#[derive(Deserialize)]
+struct UncheckedDateRange {
+    start: Date,
+    end: Date,
+}
+
+impl TryFrom<UncheckedDateRange> for DateRange {
+    type Error = DateRangeError;
+    fn try_from(value: UncheckedDateRange) -> Result<Self, Self::Error> {
+        Self::new(value.start, value.end)
+    }
+}
+
+impl DateRange {
+    /// deserialize a range saved before inverted ranges were rejected,
+    /// swapping the ends of one whose end is before its start
+    pub(crate) fn deserialize_legacy<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let UncheckedDateRange { start, end } = UncheckedDateRange::deserialize(deserializer)?;
+        Ok(Self {
+            start: start.min(end),
+            end: start.max(end),
+        })
+    }
+}
+
+impl TryFrom<(Date, Date)> for DateRange {
+    type Error = DateRangeError;
+    fn try_from(value: (Date, Date)) -> Result<Self, Self::Error> {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl DateRange {
+    pub fn new(start: Date, end: Date) -> Result<Self, DateRangeError> {
+        if end < start {
+            Err(DateRangeError::EndBeforeStart { start, end })
+        } else {
+            Ok(Self { start, end })
+        }
+    }
+
+    pub fn start(&self) -> Date {
+        self.start
+    }
+
+    pub fn end(&self) -> Date {
+        self.end
+    }
+
+    pub fn contains(&self, date: &Date) -> bool {
+        self.start <= *date && *date <= self.end
+    }
+
+    /// number of days in the range, counting both ends
+    pub fn num_days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = Date> {
+        let end = self.end;
+        self.start.iter_days().take_while(move |d| *d <= end)
+    }
+
+    /// the days in both ranges, if there are any
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end)).ok()
+    }
+
+    /// the days in either range, if together they are contiguous
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if first.end.succ_opt().is_none_or(|d| second.start <= d) {
+            Some(Self {
+                start: first.start,
+                end: first.end.max(second.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// the range moved `days` days later, or earlier if negative.
+    /// Returns `None` if it would leave the representable dates.
+    pub fn shift(&self, days: i64) -> Option<Self> {
+        let offset = Days::new(days.unsigned_abs());
+        let shift = |d: Date| {
+            if days < 0 {
+                d.checked_sub_days(offset)
+            } else {
+                d.checked_add_days(offset)
+            }
+        };
+        Some(Self {
+            start: shift(self.start)?,
+            end: shift(self.end)?,
+        })
+    }
+
+    /// the range scaled to `factor` times as many days, at least one,
+    /// keeping `anchor` (clamped into the range) the same fraction of the
+    /// way through it. Returns `None` if it would leave the representable
+    /// dates.
+    pub fn zoom(&self, anchor: Date, factor: f64) -> Option<Self> {
+        let anchor = anchor.clamp(self.start, self.end);
+        let days = self.num_days();
+        let new_days = (days as f64 * factor).round().max(1.0);
+        if !new_days.is_finite() || new_days > i32::MAX as f64 {
+            return None;
+        }
+        let new_days = new_days as i64;
+        let before = (anchor - self.start).num_days() * new_days / days;
+        let start = anchor.checked_sub_days(Days::new(before as u64))?;
+        let end = start.checked_add_days(Days::new(new_days as u64 - 1))?;
+        Some(Self { start, end })
+    }
+}
+
+/// Commonly used ranges, relative to today.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DateRangePreset {
+    ThisMonth,
+    Next30Days,
+    Next90Days,
+    YearToDate,
+    ThisYear,
+}
+
+impl DateRangePreset {
+    pub const ALL: [Self; 5] = [
+        Self::ThisMonth,
+        Self::Next30Days,
+        Self::Next90Days,
+        Self::YearToDate,
+        Self::ThisYear,
+    ];
+
+    pub fn date_range(&self, today: Date) -> DateRange {
+        use DateRangePreset::*;
+        let first_of_year = today.with_ordinal(1).unwrap();
+        let (start, end) = match self {
+            ThisMonth => {
+                let start = today.with_day(1).unwrap();
+                let next_month = start.checked_add_months(chrono::Months::new(1)).unwrap();
+                (start, next_month.pred_opt().unwrap())
+            }
+            Next30Days => (today, today + Duration::days(29)),
+            Next90Days => (today, today + Duration::days(89)),
+            YearToDate => (first_of_year, today),
+            ThisYear => (
+                first_of_year,
+                first_of_year.with_month(12).unwrap().with_day(31).unwrap(),
+            ),
+        };
+        DateRange { start, end }
+    }
+
+    /// the name used in serialized settings and form values
+    pub fn key(&self) -> &'static str {
+        use DateRangePreset::*;
+        match self {
+            ThisMonth => "this-month",
+            Next30Days => "next-30-days",
+            Next90Days => "next-90-days",
+            YearToDate => "year-to-date",
+            ThisYear => "this-year",
+        }
+    }
+}
+
+impl std::fmt::Display for DateRangePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use DateRangePreset::*;
+        match self {
+            ThisMonth => write!(f, "This month"),
+            Next30Days => write!(f, "Next 30 days"),
+            Next90Days => write!(f, "Next 90 days"),
+            YearToDate => write!(f, "Year to date"),
+            ThisYear => write!(f, "This year"),
+        }
+    }
+}
+
+impl std::str::FromStr for DateRangePreset {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|p| p.key() == s).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DateRange, DateRangeError, DateRangePreset};
+    use crate::test_util::{date, range};
+
+    #[test]
+    fn test_new_rejects_end_before_start() {
+        assert_eq!(
+            DateRange::new(date("2023-01-02"), date("2023-01-01")),
+            Err(DateRangeError::EndBeforeStart {
+                start: date("2023-01-02"),
+                end: date("2023-01-01"),
+            })
+        );
+        assert!(serde_yaml::from_str::<DateRange>("start: 2023-01-02\nend: 2023-01-01").is_err());
+        assert_eq!(
+            serde_yaml::from_str::<DateRange>("start: 2023-01-01\nend: 2023-01-01").unwrap(),
+            range("2023-01-01", "2023-01-01")
+        );
+    }
+
+    #[quickcheck]
+    fn test_contains_agrees_with_days(range: DateRange, offset: i8) -> bool {
+        let date = range.start() + chrono::Duration::days(offset as i64);
+        range.contains(&date) == range.days().any(|d| d == date)
+            && range.days().count() as i64 == range.num_days()
+    }
+
+    #[quickcheck]
+    fn test_intersection(a: DateRange, b: DateRange) -> bool {
+        match a.intersection(&b) {
+            Some(i) => i.days().all(|d| a.contains(&d) && b.contains(&d)),
+            None => !a.days().any(|d| b.contains(&d)),
+        }
+    }
+
+    #[quickcheck]
+    fn test_union(a: DateRange, b: DateRange) -> bool {
+        match a.union(&b) {
+            Some(u) => u.days().all(|d| a.contains(&d) || b.contains(&d)),
+            None => {
+                a.end() < b.start().pred_opt().unwrap() || b.end() < a.start().pred_opt().unwrap()
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift() {
+        assert_eq!(
+            range("2023-01-30", "2023-02-02").shift(-30),
+            Some(range("2022-12-31", "2023-01-03"))
+        );
+        assert_eq!(range("2023-01-01", "2023-01-02").shift(i64::MAX), None);
+    }
+
+    #[test]
+    fn test_zoom() {
+        let january = range("2023-01-01", "2023-01-31");
+        assert_eq!(
+            january.zoom(date("2023-01-11"), 0.5),
+            Some(range("2023-01-06", "2023-01-21"))
+        );
+        assert_eq!(
+            january.zoom(date("2023-01-01"), 2.0),
+            Some(range("2023-01-01", "2023-03-03"))
+        );
+        assert_eq!(
+            january.zoom(date("2023-01-20"), 0.0),
+            Some(range("2023-01-20", "2023-01-20"))
+        );
+        assert_eq!(january.zoom(date("2023-01-20"), f64::INFINITY), None);
+    }
+
+    #[quickcheck]
+    fn test_zoom_keeps_anchor(range: DateRange, offset: u8, factor: u8) -> bool {
+        let anchor = range.start() + chrono::Duration::days(offset as i64);
+        let anchor = anchor.min(range.end());
+        range
+            .zoom(anchor, factor as f64 / 16.0)
+            .is_none_or(|zoomed| zoomed.contains(&anchor))
+    }
+
+    #[test]
+    fn test_presets() {
+        let today = date("2024-02-10");
+        let cases = [
+            (
+                DateRangePreset::ThisMonth,
+                range("2024-02-01", "2024-02-29"),
+            ),
+            (
+                DateRangePreset::Next30Days,
+                range("2024-02-10", "2024-03-10"),
+            ),
+            (
+                DateRangePreset::Next90Days,
+                range("2024-02-10", "2024-05-09"),
+            ),
+            (
+                DateRangePreset::YearToDate,
+                range("2024-01-01", "2024-02-10"),
+            ),
+            (DateRangePreset::ThisYear, range("2024-01-01", "2024-12-31")),
+        ];
+        for (preset, expected) in cases {
+            assert_eq!(preset.date_range(today), expected, "{preset}");
+            assert_eq!(preset.key().parse(), Ok(preset));
+        }
+    }
+}
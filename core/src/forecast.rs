@@ -0,0 +1,280 @@
+use crate::app_state::{Date, Transaction, TransactionKind};
+use crate::date_range::DateRange;
+use crate::inflation::Inflation;
+use chrono::{Days, Months};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type ScheduleId = u16;
+
+/// How often a scheduled transaction repeats.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Frequency {
+    Weekly,
+    Fortnightly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl Frequency {
+    pub const ALL: [Self; 5] = [
+        Self::Weekly,
+        Self::Fortnightly,
+        Self::Monthly,
+        Self::Quarterly,
+        Self::Yearly,
+    ];
+
+    /// the `n`th occurrence of something first due on `start`. Months are
+    /// always counted from `start`, so a schedule on the 31st falls on the
+    /// last day of shorter months without drifting.
+    pub fn nth(&self, start: Date, n: u32) -> Option<Date> {
+        use Frequency::*;
+        match self {
+            Weekly => start.checked_add_days(Days::new(7 * n as u64)),
+            Fortnightly => start.checked_add_days(Days::new(14 * n as u64)),
+            Monthly => start.checked_add_months(Months::new(n)),
+            Quarterly => start.checked_add_months(Months::new(n.checked_mul(3)?)),
+            Yearly => start.checked_add_months(Months::new(n.checked_mul(12)?)),
+        }
+    }
+
+    /// the name used in serialized settings and form values
+    pub fn key(&self) -> &'static str {
+        use Frequency::*;
+        match self {
+            Weekly => "weekly",
+            Fortnightly => "fortnightly",
+            Monthly => "monthly",
+            Quarterly => "quarterly",
+            Yearly => "yearly",
+        }
+    }
+}
+
+impl std::fmt::Display for Frequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Frequency::*;
+        match self {
+            Weekly => write!(f, "Weekly"),
+            Fortnightly => write!(f, "Fortnightly"),
+            Monthly => write!(f, "Monthly"),
+            Quarterly => write!(f, "Quarterly"),
+            Yearly => write!(f, "Yearly"),
+        }
+    }
+}
+
+impl std::str::FromStr for Frequency {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|f| f.key() == s).ok_or(())
+    }
+}
+
+/// A transaction that repeats every `frequency`, starting on the
+/// transaction's own date and ending on `until` if given.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub transaction: Transaction,
+    pub frequency: Frequency,
+    pub until: Option<Date>,
+    /// whether the amount grows with inflation from the first occurrence
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub indexed: bool,
+}
+
+impl Schedule {
+    /// every date the transaction falls on, in order
+    pub fn occurrences(&self) -> impl Iterator<Item = Date> + '_ {
+        (0..)
+            .map_while(|n| self.frequency.nth(self.transaction.date, n))
+            .take_while(|date| self.until.is_none_or(|until| *date <= until))
+    }
+
+    pub fn occurrences_in<'a>(&'a self, date_range: &DateRange) -> impl Iterator<Item = Date> + 'a {
+        let (start, end) = (date_range.start(), date_range.end());
+        self.occurrences()
+            .skip_while(move |date| *date < start)
+            .take_while(move |date| *date <= end)
+    }
+
+    /// the transaction as it falls on `date`, grown by `inflation` since
+    /// the first occurrence if the schedule is indexed
+    pub fn on(&self, date: Date, inflation: Option<&Inflation>) -> Transaction {
+        let value = match inflation {
+            Some(inflation) if self.indexed => {
+                inflation.adjust(self.transaction.value, self.transaction.date, date)
+            }
+            _ => self.transaction.value,
+        };
+        Transaction {
+            value,
+            date,
+            ..self.transaction.clone()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScheduleRecord {
+    pub schedule: Schedule,
+    pub id: ScheduleId,
+}
+
+/// Income and expenses in one category over a baseline's window.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Trend {
+    pub income: i64,
+    pub expenses: i64,
+}
+
+impl Trend {
+    /// add `t`, or with a `sign` of -1 take it away
+    pub(crate) fn apply(&mut self, t: &Transaction, sign: i64) {
+        match t.kind {
+            TransactionKind::Income => self.income += sign * t.value as i64,
+            TransactionKind::Expense => self.expenses += sign * t.value as i64,
+        }
+    }
+}
+
+/// Income and expenses over a trailing window of history that no
+/// schedule accounts for, by category ("" for the uncategorized), each
+/// spread evenly over the days that follow the window.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Baseline {
+    pub window: DateRange,
+    pub categories: BTreeMap<String, Trend>,
+}
+
+impl Baseline {
+    /// `total` over the window's days, on the `n`th day after it,
+    /// counting from 1. Rounded so that any run of days as long as the
+    /// window adds up to exactly `total`.
+    fn share(&self, total: i64, n: i64) -> i64 {
+        let days = self.window.num_days() as i128;
+        let through = |n: i64| (total as i128 * n as i128).div_euclid(days);
+        (through(n) - through(n - 1)) as i64
+    }
+
+    /// income and expenses of every category on the `n`th day after the
+    /// window, counting from 1
+    pub fn on_day(&self, n: i64) -> (i64, i64) {
+        self.categories
+            .values()
+            .fold((0, 0), |(income, expenses), t| {
+                (
+                    income + self.share(t.income, n),
+                    expenses + self.share(t.expenses, n),
+                )
+            })
+    }
+
+    /// the average income and expenses a day of each category
+    pub fn daily_averages(&self) -> impl Iterator<Item = (&str, f64, f64)> {
+        let days = self.window.num_days() as f64;
+        self.categories.iter().map(move |(category, t)| {
+            (
+                category.as_str(),
+                t.income as f64 / days,
+                t.expenses as f64 / days,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Baseline, Frequency, Schedule, Trend};
+    use crate::app_state::{Dollars, Entry, Log, Transaction, TransactionKind};
+    use crate::date_range::DateRange;
+    use crate::granularity::Granularity;
+    use crate::test_util::{create, date, expense, range};
+
+    fn schedule(start: &str, frequency: Frequency, until: Option<&str>) -> Schedule {
+        Schedule {
+            transaction: Transaction::new(100, TransactionKind::Expense, date(start)),
+            frequency,
+            until: until.map(date),
+            indexed: false,
+        }
+    }
+
+    #[test]
+    fn test_monthly_occurrences_do_not_drift() {
+        assert_eq!(
+            schedule("2023-01-31", Frequency::Monthly, Some("2023-05-01"))
+                .occurrences()
+                .collect::<Vec<_>>(),
+            ["2023-01-31", "2023-02-28", "2023-03-31", "2023-04-30"].map(date)
+        );
+    }
+
+    #[test]
+    fn test_occurrences_in() {
+        let january = range("2023-01-10", "2023-01-31");
+        assert_eq!(
+            schedule("2023-01-02", Frequency::Weekly, None)
+                .occurrences_in(&january)
+                .collect::<Vec<_>>(),
+            ["2023-01-16", "2023-01-23", "2023-01-30"].map(date)
+        );
+    }
+
+    #[test]
+    fn test_forecast_by_category() {
+        let mut rent = schedule("2023-01-05", Frequency::Monthly, None);
+        rent.transaction.value = 500;
+        rent.transaction.category = "Rent".to_owned();
+        let log = Log::from(vec![
+            create(1000, TransactionKind::Income, "2022-12-15"),
+            Entry::Create(expense(500, "2023-01-05", "Rent")),
+            Entry::Create(expense(300, "2023-01-10", "Groceries")),
+            Entry::Create(expense(60, "2023-01-30", "Fun")),
+            Entry::Schedule(rent),
+        ]);
+
+        // the scheduled rent is left out of the trends
+        let baseline = log.baseline(30).unwrap();
+        assert_eq!(
+            baseline.daily_averages().collect::<Vec<_>>(),
+            [("Fun", 0.0, 2.0), ("Groceries", 0.0, 10.0)]
+        );
+
+        // from the 31st each day spends 12, and the rent is paid on the 5th
+        let quarter = range("2023-01-01", "2023-03-31");
+        let months = log
+            .forecast_data_by(&quarter, Granularity::Month, 30)
+            .iter()
+            .map(|s| (s.expenses, s.balance))
+            .collect::<Vec<(Dollars, Dollars)>>();
+        // the balance carries the income from before the range
+        assert_eq!(months, [(872, 128), (836, -708), (872, -1580)]);
+    }
+
+    #[quickcheck]
+    fn test_baseline_spreads_totals_exactly(categories: Vec<(i32, i32)>, days: u8, n: u16) -> bool {
+        let start = date("2023-01-01");
+        let baseline = Baseline {
+            window: DateRange::new(start, start + chrono::Duration::days(days as i64)).unwrap(),
+            categories: categories
+                .iter()
+                .enumerate()
+                .map(|(i, (income, expenses))| {
+                    let trend = Trend {
+                        income: *income as i64,
+                        expenses: *expenses as i64,
+                    };
+                    (i.to_string(), trend)
+                })
+                .collect(),
+        };
+        let (income, expenses) = (n as i64 + 1..=n as i64 + baseline.window.num_days())
+            .map(|day| baseline.on_day(day))
+            .fold((0, 0), |(i, e), (di, de)| (i + di, e + de));
+        income == categories.iter().map(|c| c.0 as i64).sum::<i64>()
+            && expenses == categories.iter().map(|c| c.1 as i64).sum::<i64>()
+    }
+}
@@ -0,0 +1,174 @@
+use crate::app_state::Date;
+use crate::date_range::DateRange;
+use chrono::{Datelike, Months, Weekday};
+
+/// The length of the periods a timeline is divided into.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Granularity {
+    #[default]
+    Day,
+    /// weeks beginning on the given day
+    Week(Weekday),
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Granularity {
+    /// the whole period that `date` falls in
+    pub fn period_containing(&self, date: Date) -> DateRange {
+        use Granularity::*;
+        let (start, end) = match self {
+            Day => (date, date),
+            Week(week_start) => {
+                let offset = (date.weekday().num_days_from_monday() + 7
+                    - week_start.num_days_from_monday())
+                    % 7;
+                let start = date - chrono::Duration::days(offset as i64);
+                (start, start + chrono::Duration::days(6))
+            }
+            Month => months(date.with_day(1).unwrap(), 1),
+            Quarter => {
+                let first_month = (date.month0() / 3) * 3 + 1;
+                months(Date::from_ymd_opt(date.year(), first_month, 1).unwrap(), 3)
+            }
+            Year => months(date.with_ordinal(1).unwrap(), 12),
+        };
+        DateRange::new(start, end).unwrap()
+    }
+
+    /// the periods covering `date_range`, with the first and last
+    /// clipped to it
+    pub fn periods(&self, date_range: &DateRange) -> Vec<DateRange> {
+        let mut periods = Vec::new();
+        let mut date = date_range.start();
+        loop {
+            let period = self
+                .period_containing(date)
+                .intersection(date_range)
+                .unwrap();
+            let next = period.end().succ_opt();
+            periods.push(period);
+            match next {
+                Some(next) if next <= date_range.end() => date = next,
+                _ => return periods,
+            }
+        }
+    }
+
+    /// the name used in form values, ignoring the week start
+    pub fn key(&self) -> &'static str {
+        use Granularity::*;
+        match self {
+            Day => "day",
+            Week(_) => "week",
+            Month => "month",
+            Quarter => "quarter",
+            Year => "year",
+        }
+    }
+
+    /// parse a `key`, using `week_start` if it names weeks
+    pub fn from_key(key: &str, week_start: Weekday) -> Option<Self> {
+        use Granularity::*;
+        match key {
+            "day" => Some(Day),
+            "week" => Some(Week(week_start)),
+            "month" => Some(Month),
+            "quarter" => Some(Quarter),
+            "year" => Some(Year),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Granularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Granularity::*;
+        match self {
+            Day => write!(f, "Day"),
+            Week(_) => write!(f, "Week"),
+            Month => write!(f, "Month"),
+            Quarter => write!(f, "Quarter"),
+            Year => write!(f, "Year"),
+        }
+    }
+}
+
+/// `n` whole months beginning on `start`
+fn months(start: Date, n: u32) -> (Date, Date) {
+    let next = start.checked_add_months(Months::new(n)).unwrap();
+    (start, next.pred_opt().unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use super::Granularity;
+    use crate::app_state::Date;
+    use crate::date_range::DateRange;
+    use crate::test_util::range;
+    use chrono::Weekday;
+
+    #[test]
+    fn test_period_containing() {
+        // a Thursday
+        let date: Date = "2024-02-29".parse().unwrap();
+        let cases = [
+            (Granularity::Day, range("2024-02-29", "2024-02-29")),
+            (
+                Granularity::Week(Weekday::Mon),
+                range("2024-02-26", "2024-03-03"),
+            ),
+            (
+                Granularity::Week(Weekday::Sun),
+                range("2024-02-25", "2024-03-02"),
+            ),
+            (
+                Granularity::Week(Weekday::Thu),
+                range("2024-02-29", "2024-03-06"),
+            ),
+            (Granularity::Month, range("2024-02-01", "2024-02-29")),
+            (Granularity::Quarter, range("2024-01-01", "2024-03-31")),
+            (Granularity::Year, range("2024-01-01", "2024-12-31")),
+        ];
+        for (granularity, expected) in cases {
+            assert_eq!(
+                granularity.period_containing(date),
+                expected,
+                "{granularity:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_periods_are_clipped() {
+        assert_eq!(
+            Granularity::Month.periods(&range("2023-01-15", "2023-03-10")),
+            vec![
+                range("2023-01-15", "2023-01-31"),
+                range("2023-02-01", "2023-02-28"),
+                range("2023-03-01", "2023-03-10"),
+            ]
+        );
+    }
+
+    // the periods partition the range, in order
+    #[quickcheck]
+    fn test_periods_partition_range(date_range: DateRange) -> bool {
+        [
+            Granularity::Day,
+            Granularity::Week(Weekday::Wed),
+            Granularity::Month,
+            Granularity::Quarter,
+            Granularity::Year,
+        ]
+        .iter()
+        .all(|granularity| {
+            granularity
+                .periods(&date_range)
+                .iter()
+                .flat_map(|p| p.days())
+                .eq(date_range.days())
+        })
+    }
+}
@@ -0,0 +1,185 @@
+use crate::app_state::{Date, DateSummary, Dollars, TimelineData};
+use crate::interest::MAX_RATE;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How fast prices rise, as yearly percentages: `rate` every year except
+/// those given their own rate in `by_year`. Used to grow recurring
+/// amounts and to express projections in one day's dollars.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct Inflation {
+    pub rate: f64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_year: BTreeMap<i32, f64>,
+}
+
+impl Inflation {
+    /// whether prices can change by `rate` a year: they can't fall by all
+    /// they are worth or more, nor grow by more than `MAX_RATE`
+    pub fn is_valid_rate(rate: f64) -> bool {
+        rate > -100.0 && rate <= MAX_RATE
+    }
+
+    /// the first rate, of every year or of one, that isn't valid, if any
+    pub fn invalid_rate(&self) -> Option<f64> {
+        std::iter::once(self.rate)
+            .chain(self.by_year.values().copied())
+            .find(|rate| !Self::is_valid_rate(*rate))
+    }
+
+    pub fn rate_in(&self, year: i32) -> f64 {
+        self.by_year.get(&year).copied().unwrap_or(self.rate)
+    }
+
+    /// how many times over prices grow from `from` to `to`, which is less
+    /// than one if `to` is earlier. Each year's rate is spread evenly over
+    /// its days. A year whose rate isn't valid leaves prices as they were,
+    /// though a `Log` never holds one.
+    pub fn factor(&self, from: Date, to: Date) -> f64 {
+        if to < from {
+            return 1.0 / self.factor(to, from);
+        }
+        let mut growth = 0.0;
+        let mut date = from;
+        while date < to {
+            let year = date.year();
+            let (Some(start), Some(next)) = (
+                Date::from_ymd_opt(year, 1, 1),
+                Date::from_ymd_opt(year + 1, 1, 1),
+            ) else {
+                break;
+            };
+            let end = next.min(to);
+            let share = (end - date).num_days() as f64 / (next - start).num_days() as f64;
+            let rate = Some(self.rate_in(year)).filter(|r| Self::is_valid_rate(*r));
+            growth += share * (1.0 + rate.unwrap_or_default() / 100.0).ln();
+            date = end;
+        }
+        growth.exp()
+    }
+
+    /// what `value` on `from` is worth in the dollars of `to`
+    pub fn adjust(&self, value: Dollars, from: Date, to: Date) -> Dollars {
+        (value as f64 * self.factor(from, to))
+            .round()
+            .clamp(Dollars::MIN as f64, Dollars::MAX as f64) as Dollars
+    }
+}
+
+impl TimelineData {
+    /// the summaries in the dollars of `base`, e.g. today's: the flows of
+    /// each period as of its middle day, and the balance as of its end
+    pub fn in_dollars_of(&self, inflation: &Inflation, base: Date) -> TimelineData {
+        self.iter()
+            .map(|s| {
+                let middle =
+                    s.period.start() + chrono::Duration::days((s.period.num_days() - 1) / 2);
+                let flow = |value| inflation.adjust(value, middle, base);
+                DateSummary {
+                    period: s.period.clone(),
+                    income: flow(s.income),
+                    expenses: flow(s.expenses),
+                    balance: inflation.adjust(s.balance, s.period.end(), base),
+                    interest: flow(s.interest),
+                    forecast: s.forecast,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Inflation;
+    use crate::app_state::{AppendError, Dollars, Entry, Log, Transaction, TransactionKind};
+    use crate::forecast::{Frequency, Schedule};
+    use crate::granularity::Granularity;
+    use crate::test_util::{date, range};
+
+    fn inflation() -> Inflation {
+        Inflation {
+            rate: 3.0,
+            by_year: [(2024, 10.0)].into(),
+        }
+    }
+
+    #[test]
+    fn test_factor() {
+        let inflation = inflation();
+        let factor = |from, to| inflation.factor(date(from), date(to));
+        assert!((factor("2023-01-01", "2024-01-01") - 1.03).abs() < 1e-9);
+        assert!((factor("2023-01-01", "2025-01-01") - 1.03 * 1.1).abs() < 1e-9);
+        assert!((factor("2025-01-01", "2023-01-01") - 1.0 / (1.03 * 1.1)).abs() < 1e-9);
+        // half of 2024, a leap year
+        assert!((factor("2024-01-01", "2024-07-02") - 1.1f64.sqrt()).abs() < 1e-9);
+        assert_eq!(
+            inflation.adjust(1000, date("2025-01-01"), date("2023-01-01")),
+            883
+        );
+    }
+
+    #[test]
+    fn test_indexed_schedule() {
+        let schedule = |indexed| {
+            Entry::Schedule(Schedule {
+                transaction: Transaction::new(1000, TransactionKind::Expense, date("2023-01-01")),
+                frequency: Frequency::Yearly,
+                until: None,
+                indexed,
+            })
+        };
+        let years = range("2023-01-01", "2025-12-31");
+        let expenses = |log: &Log| {
+            log.forecast_data_by(&years, Granularity::Year, 90)
+                .iter()
+                .map(|s| s.expenses)
+                .collect::<Vec<Dollars>>()
+        };
+        let mut log = Log::from(vec![schedule(true), schedule(false)]);
+        // nothing grows until there is a rate
+        assert_eq!(expenses(&log), [2000, 2000, 2000]);
+        log.append(Entry::SetInflation(Some(inflation()))).unwrap();
+        assert_eq!(expenses(&log), [2000, 2030, 2133]);
+
+        // in the dollars of mid 2023, each year is deflated from its middle
+        let data = log.forecast_data_by(&years, Granularity::Year, 90);
+        let real = data.in_dollars_of(&inflation(), date("2023-07-02"));
+        assert_eq!(
+            real.iter().map(|s| s.expenses).collect::<Vec<_>>(),
+            [2000, 1908, 1883]
+        );
+        assert_eq!(real.iter().last().unwrap().balance, -5360);
+    }
+
+    #[test]
+    fn test_invalid_rates() {
+        let mut log = Log::default();
+        for rate in [-100.0, -150.0, f64::NAN, f64::INFINITY] {
+            let by_year = Inflation {
+                by_year: [(2024, rate)].into(),
+                ..inflation()
+            };
+            for inflation in [
+                Inflation {
+                    rate,
+                    ..inflation()
+                },
+                by_year,
+            ] {
+                assert!(matches!(
+                    log.append(Entry::SetInflation(Some(inflation))),
+                    Err(AppendError::InvalidRate(_))
+                ));
+            }
+        }
+        assert!(log.inflation().is_none());
+
+        // a year of prices falling to nothing leaves them as they were
+        let crash = Inflation {
+            rate: 0.0,
+            by_year: [(2024, -100.0)].into(),
+        };
+        assert_eq!(crash.factor(date("2023-01-01"), date("2025-01-01")), 1.0);
+    }
+}
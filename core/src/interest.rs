@@ -0,0 +1,274 @@
+use crate::app_state::Date;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+/// How a yearly rate is quoted.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum RateBasis {
+    /// the nominal rate, divided evenly between compounding periods
+    Apr,
+    /// the effective rate after a year's compounding
+    Apy,
+}
+
+/// How often accrued interest is added to the balance.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Compounding {
+    Daily,
+    /// on the last day of each month
+    Monthly,
+}
+
+impl RateBasis {
+    pub const ALL: [Self; 2] = [Self::Apr, Self::Apy];
+
+    /// the name used in form values
+    pub fn key(&self) -> &'static str {
+        match self {
+            RateBasis::Apr => "apr",
+            RateBasis::Apy => "apy",
+        }
+    }
+}
+
+impl std::fmt::Display for RateBasis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateBasis::Apr => write!(f, "APR"),
+            RateBasis::Apy => write!(f, "APY"),
+        }
+    }
+}
+
+impl Compounding {
+    pub const ALL: [Self; 2] = [Self::Daily, Self::Monthly];
+
+    /// the name used in form values
+    pub fn key(&self) -> &'static str {
+        match self {
+            Compounding::Daily => "daily",
+            Compounding::Monthly => "monthly",
+        }
+    }
+
+    fn periods_per_year(&self) -> f64 {
+        match self {
+            Compounding::Daily => 365.0,
+            Compounding::Monthly => 12.0,
+        }
+    }
+}
+
+impl std::fmt::Display for Compounding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compounding::Daily => write!(f, "Daily"),
+            Compounding::Monthly => write!(f, "Monthly"),
+        }
+    }
+}
+
+/// The highest yearly rate, as a percentage, accepted for loans, interest
+/// and inflation; amounts soon stop fitting in `Dollars` beyond it.
+pub const MAX_RATE: f64 = 1000.0;
+
+/// The interest the account earns while its balance is positive, and is
+/// charged while it is overdrawn, as on a credit line. Rates are yearly
+/// percentages, e.g. 4.5; a rate of zero pays or charges nothing.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Interest {
+    pub savings_rate: f64,
+    pub credit_rate: f64,
+    pub basis: RateBasis,
+    pub compounding: Compounding,
+}
+
+impl Interest {
+    /// the first rate that isn't from zero to `MAX_RATE`, if any
+    pub fn invalid_rate(&self) -> Option<f64> {
+        [self.savings_rate, self.credit_rate]
+            .into_iter()
+            .find(|rate| !(0.0..=MAX_RATE).contains(rate))
+    }
+
+    /// the rate per compounding period of a yearly percentage
+    fn periodic_rate(&self, rate: f64) -> f64 {
+        let n = self.compounding.periods_per_year();
+        match self.basis {
+            RateBasis::Apr => rate / 100.0 / n,
+            RateBasis::Apy => (1.0 + rate / 100.0).powf(1.0 / n) - 1.0,
+        }
+    }
+
+    /// the interest accrued over one day on an end of day `balance`; under
+    /// monthly compounding each day accrues its share of the month
+    fn accrued_on(&self, balance: f64, date: Date) -> f64 {
+        let rate = if balance >= 0.0 {
+            self.savings_rate
+        } else {
+            self.credit_rate
+        };
+        let share = match self.compounding {
+            Compounding::Daily => 1.0,
+            Compounding::Monthly => 1.0 / days_in_month(date) as f64,
+        };
+        balance * self.periodic_rate(rate) * share
+    }
+
+    fn compounds_on(&self, date: Date) -> bool {
+        match self.compounding {
+            Compounding::Daily => true,
+            Compounding::Monthly => date.day() == days_in_month(date),
+        }
+    }
+}
+
+fn days_in_month(date: Date) -> u32 {
+    let first = date.with_day(1).unwrap();
+    let next = first
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(Date::MAX);
+    (next - first).num_days() as u32
+}
+
+/// Interest accruing day by day on a running balance, and paid into it in
+/// whole dollars as it compounds. The part of a payment rounded away is
+/// carried over, and still earns interest.
+#[derive(Debug, Clone)]
+pub(crate) struct Accrual {
+    interest: Interest,
+    /// accrued since interest last compounded
+    accrued: f64,
+    carried: f64,
+}
+
+impl Accrual {
+    pub(crate) fn new(interest: Interest) -> Self {
+        Self {
+            interest,
+            accrued: 0.0,
+            carried: 0.0,
+        }
+    }
+
+    /// accrue a day's interest on the end of day `balance`, returning what
+    /// is paid into the balance that day, or charged if negative
+    pub(crate) fn day(&mut self, balance: i64, date: Date) -> i64 {
+        self.accrued += self
+            .interest
+            .accrued_on(balance as f64 + self.carried, date);
+        if !self.interest.compounds_on(date) {
+            return 0;
+        }
+        // a balance grown past what `i64` holds stops growing there
+        let due = (self.accrued + self.carried).clamp(i64::MIN as f64, i64::MAX as f64);
+        let paid = due.round();
+        self.accrued = 0.0;
+        self.carried = due - paid;
+        paid as i64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Compounding, Interest, RateBasis};
+    use crate::app_state::{AppendError, Dollars, Entry, Log, TransactionKind};
+    use crate::granularity::Granularity;
+    use crate::test_util::{create, range};
+
+    fn interest(rate: f64, basis: RateBasis, compounding: Compounding) -> Entry {
+        Entry::SetInterest(Some(Interest {
+            savings_rate: rate,
+            credit_rate: 20.0,
+            basis,
+            compounding,
+        }))
+    }
+
+    /// the balance and interest of each month of 2023
+    fn months(log: &Log) -> Vec<(Dollars, Dollars)> {
+        let year = range("2023-01-01", "2023-12-31");
+        log.timeline_data_by(&year, Granularity::Month)
+            .iter()
+            .map(|s| (s.balance, s.interest))
+            .collect()
+    }
+
+    #[test]
+    fn test_savings() {
+        use Compounding::*;
+        use RateBasis::*;
+        let mut log = Log::from(vec![create(10000, TransactionKind::Income, "2023-01-01")]);
+        assert!(months(&log).iter().all(|m| *m == (10000, 0)));
+
+        // an APY earns its rate over the year however it compounds
+        for compounding in [Daily, Monthly] {
+            log.append(interest(5.0, Apy, compounding)).unwrap();
+            assert_eq!(months(&log).last().unwrap().0, 10500, "{compounding}");
+        }
+
+        // an APR compounded monthly earns a little more than its rate
+        log.append(interest(5.0, Apr, Monthly)).unwrap();
+        let apr = months(&log);
+        assert_eq!(apr[0], (10042, 42));
+        assert_eq!(apr[11].0, 10512);
+        assert_eq!(apr.iter().map(|m| m.1).sum::<Dollars>(), 512);
+        // income and expenses are left as recorded
+        assert_eq!(
+            log.timeline_data_by(&log.date_range(), Granularity::Month)
+                .iter()
+                .next()
+                .unwrap()
+                .income,
+            10000
+        );
+
+        log.append(Entry::SetInterest(None)).unwrap();
+        assert_eq!(months(&log).last().unwrap().0, 10000);
+    }
+
+    #[test]
+    fn test_credit_line() {
+        let log = Log::from(vec![
+            create(1000, TransactionKind::Expense, "2023-01-01"),
+            interest(5.0, RateBasis::Apr, Compounding::Monthly),
+        ]);
+        let credit = months(&log);
+        // 20% a year on 1000 overdrawn
+        assert_eq!(credit[0], (-1017, -17));
+        assert!(credit[11].0 < -1200);
+    }
+
+    #[test]
+    fn test_unbounded_growth() {
+        let decade = range("2023-01-01", "2032-12-31");
+        for rate in [500.0, super::MAX_RATE] {
+            let log = Log::from(vec![
+                create(10000, TransactionKind::Income, "2023-01-01"),
+                interest(rate, RateBasis::Apr, Compounding::Daily),
+            ]);
+            // the balance stops at the most `Dollars` hold rather than overflowing
+            let data = log.timeline_data_by(&decade, Granularity::Year);
+            assert_eq!(data.iter().last().unwrap().balance, Dollars::MAX, "{rate}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_rates() {
+        let mut log = Log::from(Entry::Fork("what if".to_owned()));
+        for rate in [f64::NAN, f64::INFINITY, -1.0, super::MAX_RATE + 1.0] {
+            let entry = interest(rate, RateBasis::Apr, Compounding::Daily);
+            assert!(matches!(
+                log.append(entry.clone()),
+                Err(AppendError::InvalidRate(_))
+            ));
+            // nor can a scenario set one
+            assert!(matches!(
+                log.append(Entry::Branch("what if".to_owned(), Box::new(entry))),
+                Err(AppendError::InvalidRate(_))
+            ));
+        }
+        assert_eq!(log.entries().len(), 1);
+        assert!(log.interest().is_none());
+    }
+}
@@ -0,0 +1,42 @@
+//! The planning logic behind the financial planner: the append-only
+//! [`Log`] of [`Entry`]s, the projections computed from it, and the
+//! document format it is stored in. Nothing here depends on a UI; the
+//! `yew` feature adds the `Reducible` impl the web app dispatches to.
+
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+mod app_state;
+mod date_range;
+mod forecast;
+mod granularity;
+mod inflation;
+mod interest;
+mod loan;
+pub mod log_file;
+mod low_balance;
+mod query;
+mod reconcile;
+mod rules;
+mod scenario;
+pub mod stored_log;
+#[cfg(test)]
+mod test_util;
+mod variance;
+pub mod vault;
+
+pub use app_state::*;
+pub use date_range::*;
+pub use forecast::*;
+pub use granularity::*;
+pub use inflation::*;
+pub use interest::*;
+pub use loan::*;
+pub use low_balance::*;
+pub use query::*;
+pub use reconcile::*;
+pub use rules::*;
+pub use variance::*;
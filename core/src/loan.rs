@@ -0,0 +1,354 @@
+use crate::app_state::{Date, Dollars, Transaction, TransactionKind};
+use crate::forecast::Frequency;
+use crate::interest::MAX_RATE;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LoanError {
+    NoPrincipal,
+    NoTerm,
+    /// the rate is negative, infinite, above `MAX_RATE` or not a number
+    InvalidRate(f64),
+    /// the term is longer than `MAX_TERM_MONTHS`
+    TermTooLong,
+    /// a payment is too large to be represented
+    TooLarge,
+    /// the payments don't cover the interest, so would never repay it
+    PaymentTooSmall,
+    /// a payment would fall after the last date that can be represented
+    DateOutOfRange,
+}
+
+impl std::fmt::Display for LoanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use LoanError::*;
+        match self {
+            NoPrincipal => write!(f, "the principal must be more than zero"),
+            NoTerm => write!(f, "the term must be at least one month"),
+            InvalidRate(rate) => write!(f, "{rate} is not a valid interest rate"),
+            TermTooLong => write!(f, "the term can be at most {MAX_TERM_MONTHS} months"),
+            TooLarge => write!(f, "the payments are too large"),
+            PaymentTooSmall => write!(f, "the payments don't cover the interest"),
+            DateOutOfRange => write!(f, "the payments run past the last supported date"),
+        }
+    }
+}
+
+impl std::error::Error for LoanError {}
+
+/// The longest term accepted, a hundred years.
+pub const MAX_TERM_MONTHS: u32 = 1200;
+
+/// A loan repaid in equal monthly payments, such as a mortgage or a car
+/// loan. Interest accrues monthly on what is still owed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Loan {
+    /// the payee of the payments
+    pub name: String,
+    pub principal: Dollars,
+    /// the yearly rate as a percentage, e.g. 6.5
+    pub annual_rate: f64,
+    pub term_months: u32,
+    pub first_payment: Date,
+    /// paid on top of every payment, off the principal
+    pub extra: Dollars,
+    /// one-off payments off the principal, each made with the first
+    /// payment on or after its date
+    pub lump_sums: Vec<(Date, Dollars)>,
+}
+
+/// One monthly payment on a loan.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LoanPayment {
+    /// counted from 1
+    pub number: u32,
+    pub date: Date,
+    pub principal: Dollars,
+    pub interest: Dollars,
+    /// what is still owed after the payment
+    pub balance: Dollars,
+}
+
+impl Loan {
+    fn monthly_rate(&self) -> f64 {
+        self.annual_rate / 100.0 / 12.0
+    }
+
+    /// a month's interest on `balance`
+    fn interest_on(&self, balance: i64) -> i64 {
+        (balance as f64 * self.monthly_rate()).round() as i64
+    }
+
+    /// the payment that repays the loan over its term, before any extra,
+    /// rounded up to a whole dollar, or why the loan can't be repaid
+    pub fn regular_payment(&self) -> Result<Dollars, LoanError> {
+        if self.principal <= 0 {
+            return Err(LoanError::NoPrincipal);
+        }
+        if self.term_months == 0 {
+            return Err(LoanError::NoTerm);
+        }
+        if self.term_months > MAX_TERM_MONTHS {
+            return Err(LoanError::TermTooLong);
+        }
+        if !(0.0..=MAX_RATE).contains(&self.annual_rate) {
+            return Err(LoanError::InvalidRate(self.annual_rate));
+        }
+        let payment = self.payment();
+        if payment > Dollars::MAX as f64 {
+            return Err(LoanError::TooLarge);
+        }
+        let payment = payment as Dollars;
+        // the interest only gets smaller as the balance is paid down
+        if payment as i64 + self.extra as i64 <= self.interest_on(self.principal as i64) {
+            return Err(LoanError::PaymentTooSmall);
+        }
+        Ok(payment)
+    }
+
+    fn payment(&self) -> f64 {
+        let (principal, n, r) = (
+            self.principal as f64,
+            self.term_months as f64,
+            self.monthly_rate(),
+        );
+        let payment = if r == 0.0 {
+            principal / n
+        } else {
+            principal * r / (1.0 - (1.0 + r).powf(-n))
+        };
+        payment.ceil()
+    }
+
+    /// every payment until the loan is repaid. Extra payments bring the
+    /// payoff date forward; the last payment is whatever is left.
+    pub fn schedule(&self) -> Result<Vec<LoanPayment>, LoanError> {
+        let regular = self.regular_payment()? as i64;
+        let mut balance = self.principal as i64;
+        let mut previous: Option<Date> = None;
+        let mut payments = Vec::new();
+        for number in 1..=self.term_months {
+            let date = Frequency::Monthly
+                .nth(self.first_payment, number - 1)
+                .ok_or(LoanError::DateOutOfRange)?;
+            let interest = self.interest_on(balance);
+            let lump_sums: i64 = self
+                .lump_sums
+                .iter()
+                .filter(|(d, _)| *d <= date && previous.is_none_or(|p| *d > p))
+                .map(|(_, value)| *value as i64)
+                .sum();
+            // rounding can leave a little owing at the end of the term
+            let principal = if number == self.term_months {
+                balance
+            } else {
+                (regular - interest + self.extra as i64 + lump_sums).clamp(0, balance)
+            };
+            balance -= principal;
+            let dollars = |value: i64| Dollars::try_from(value).map_err(|_| LoanError::TooLarge);
+            payments.push(LoanPayment {
+                number,
+                date,
+                principal: dollars(principal)?,
+                interest: dollars(interest)?,
+                balance: dollars(balance)?,
+            });
+            if balance == 0 {
+                break;
+            }
+            previous = Some(date);
+        }
+        Ok(payments)
+    }
+
+    /// the schedule as expenses to add to the log, with the principal and
+    /// the interest of each payment as separate transactions
+    pub fn transactions(&self) -> Result<Vec<Transaction>, LoanError> {
+        let payments = self.schedule()?;
+        let count = payments.len();
+        let transaction = |value, date, category: &str, number| Transaction {
+            payee: self.name.clone(),
+            memo: format!("payment {number} of {count}"),
+            category: category.to_owned(),
+            ..Transaction::new(value, TransactionKind::Expense, date)
+        };
+        Ok(payments
+            .iter()
+            .flat_map(|p| {
+                [
+                    transaction(p.principal, p.date, "Loan principal", p.number),
+                    transaction(p.interest, p.date, "Loan interest", p.number),
+                ]
+            })
+            .filter(|t| t.value > 0)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Loan, LoanError, LoanPayment};
+    use crate::app_state::Dollars;
+    use crate::test_util::date;
+
+    fn loan() -> Loan {
+        Loan {
+            name: "Bank".to_owned(),
+            principal: 1000,
+            annual_rate: 12.0,
+            term_months: 12,
+            first_payment: date("2023-01-31"),
+            extra: 0,
+            lump_sums: vec![],
+        }
+    }
+
+    #[test]
+    fn test_schedule() {
+        let loan = loan();
+        assert_eq!(loan.regular_payment(), Ok(89));
+        let schedule = loan.schedule().unwrap();
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(
+            schedule[0],
+            LoanPayment {
+                number: 1,
+                date: date("2023-01-31"),
+                principal: 79,
+                interest: 10,
+                balance: 921,
+            }
+        );
+        // months are counted from the first payment
+        assert_eq!(schedule[1].date, date("2023-02-28"));
+        assert_eq!(schedule[2].date, date("2023-03-31"));
+        let last = schedule.last().unwrap();
+        assert_eq!(last.date, date("2023-12-31"));
+        assert_eq!(last.balance, 0);
+        assert_eq!(
+            schedule.iter().map(|p| p.principal).sum::<i32>(),
+            loan.principal
+        );
+
+        let transactions = loan.transactions().unwrap();
+        assert_eq!(transactions.len(), 24);
+        assert_eq!(transactions[0].value, 79);
+        assert_eq!(transactions[0].category, "Loan principal");
+        assert_eq!(transactions[1].value, 10);
+        assert_eq!(transactions[1].memo, "payment 1 of 12");
+    }
+
+    #[test]
+    fn test_extra_payments() {
+        let base = loan().schedule().unwrap();
+        let interest = |s: &[LoanPayment]| s.iter().map(|p| p.interest).sum::<i32>();
+
+        let extra = Loan {
+            extra: 50,
+            ..loan()
+        }
+        .schedule()
+        .unwrap();
+        assert_eq!(extra.len(), 8);
+        assert!(interest(&extra) < interest(&base));
+
+        // a lump sum goes with the next payment on or after its date
+        let lump = Loan {
+            lump_sums: vec![(date("2023-02-15"), 500)],
+            ..loan()
+        }
+        .schedule()
+        .unwrap();
+        assert_eq!(lump[1].principal, base[1].principal + 500);
+        assert_eq!(lump.len(), 6);
+    }
+
+    #[test]
+    fn test_interest_free() {
+        let loan = Loan {
+            annual_rate: 0.0,
+            term_months: 3,
+            ..loan()
+        };
+        assert_eq!(loan.regular_payment(), Ok(334));
+        let principal = loan
+            .schedule()
+            .unwrap()
+            .iter()
+            .map(|p| p.principal)
+            .collect::<Vec<_>>();
+        assert_eq!(principal, [334, 334, 332]);
+        // no interest transactions
+        assert_eq!(loan.transactions().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid() {
+        // the payment is rejected along with the schedule
+        let invalid = |loan: Loan| {
+            let e = loan.schedule().unwrap_err();
+            // as messages, since a rate that isn't a number isn't equal to itself
+            assert_eq!(
+                loan.regular_payment().unwrap_err().to_string(),
+                e.to_string()
+            );
+            e
+        };
+        assert_eq!(
+            invalid(Loan {
+                principal: 0,
+                ..loan()
+            }),
+            LoanError::NoPrincipal
+        );
+        assert_eq!(
+            invalid(Loan {
+                term_months: 0,
+                ..loan()
+            }),
+            LoanError::NoTerm
+        );
+        for annual_rate in [f64::NAN, f64::INFINITY, -1.0, 1e6] {
+            assert!(matches!(
+                invalid(Loan {
+                    annual_rate,
+                    ..loan()
+                }),
+                LoanError::InvalidRate(_)
+            ));
+        }
+        assert_eq!(
+            invalid(Loan {
+                principal: Dollars::MAX,
+                annual_rate: 1000.0,
+                term_months: 2,
+                ..loan()
+            }),
+            LoanError::TooLarge
+        );
+        assert_eq!(
+            invalid(Loan {
+                annual_rate: 0.0,
+                term_months: 4_000_000,
+                ..loan()
+            }),
+            LoanError::TermTooLong
+        );
+        // 835.83 a month in interest, which the payment rounds up to
+        assert_eq!(
+            invalid(Loan {
+                principal: 1003,
+                annual_rate: 1000.0,
+                term_months: 120,
+                ..loan()
+            }),
+            LoanError::PaymentTooSmall
+        );
+        assert_eq!(
+            invalid(Loan {
+                extra: -89,
+                ..loan()
+            }),
+            LoanError::PaymentTooSmall
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::app_state::Log;
+use crate::stored_log::{self, StoredLog};
+use crate::vault::Vault;
+use std::{error::Error, fs, io::ErrorKind, path::PathBuf};
+
+/// A log read from a YAML file in the web app's export format. Remembers
+/// the key an encrypted file was opened with so saving keeps it encrypted.
+pub struct LogFile {
+    path: PathBuf,
+    vault: Option<Vault>,
+    pub log: Log,
+}
+
+impl LogFile {
+    /// open the log at `path`, or start an empty one if the file does
+    /// not exist yet. A passphrase for a file that is not encrypted is an
+    /// error rather than ignored, so the file is never taken to be safe.
+    pub fn open(path: PathBuf, passphrase: Option<&str>) -> Result<Self, Box<dyn Error>> {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let vault = passphrase.map(Vault::create).transpose()?;
+                return Ok(Self {
+                    path,
+                    vault,
+                    log: Log::default(),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let (vault, entries) = match StoredLog::from_yaml(&contents)? {
+            StoredLog::Plain(_) if passphrase.is_some() => {
+                return Err("log is not encrypted; leave out the passphrase".into())
+            }
+            StoredLog::Plain(entries) => (None, entries),
+            StoredLog::Encrypted(sealed) => {
+                let passphrase = passphrase.ok_or("log is encrypted; a passphrase is required")?;
+                let (vault, entries) = stored_log::unlock(passphrase, &sealed)?;
+                (Some(vault), entries)
+            }
+        };
+        Ok(Self {
+            path,
+            vault,
+            log: Log::from(entries),
+        })
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let yaml = StoredLog::new(self.log.entries(), self.vault.as_ref())?.to_yaml()?;
+        fs::write(&self.path, yaml)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LogFile;
+    use crate::app_state::{Entry, Transaction, TransactionKind};
+
+    #[test]
+    fn test_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.yaml");
+        let mut plain = LogFile::open(path.clone(), None).unwrap();
+        plain
+            .log
+            .append(Entry::Create(Transaction::new(
+                100,
+                TransactionKind::Income,
+                "2023-01-02".parse().unwrap(),
+            )))
+            .unwrap();
+        plain.save().unwrap();
+        assert_eq!(
+            LogFile::open(path.clone(), Some("passphrase"))
+                .err()
+                .unwrap()
+                .to_string(),
+            "log is not encrypted; leave out the passphrase"
+        );
+        assert_eq!(
+            LogFile::open(path, None).unwrap().log.entries(),
+            plain.log.entries()
+        );
+
+        let path = dir.path().join("encrypted.yaml");
+        let encrypted = LogFile::open(path.clone(), Some("passphrase")).unwrap();
+        encrypted.save().unwrap();
+        assert!(LogFile::open(path.clone(), None).is_err());
+        assert!(LogFile::open(path, Some("passphrase")).is_ok());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::app_state::{DateSummary, Dollars, TimelineData};
+
+/// Where the balance of a timeline runs low. Each field is the summary of
+/// the period in question, so with day granularity its `period` is the
+/// single date it happens on.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LowBalance {
+    /// the first period ending overdrawn
+    pub below_zero: Option<DateSummary>,
+    /// the first period ending below the threshold
+    pub below_threshold: Option<DateSummary>,
+    /// the earliest period ending on the lowest balance
+    pub minimum: Option<DateSummary>,
+}
+
+impl TimelineData {
+    pub fn low_balance(&self, threshold: Dollars) -> LowBalance {
+        let first_below = |limit: Dollars| self.iter().find(|s| s.balance < limit).cloned();
+        LowBalance {
+            below_zero: first_below(0),
+            below_threshold: first_below(threshold),
+            // `min_by_key` keeps the first of equal elements
+            minimum: self.iter().min_by_key(|s| s.balance).cloned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app_state::{DateSummary, Entry, Log, TransactionKind};
+    use crate::test_util::{create, date, range};
+
+    #[test]
+    fn test_low_balance() {
+        use TransactionKind::*;
+        let log = Log::from(vec![
+            Entry::SetDate(range("2023-01-02", "2023-01-10")),
+            create(100, Income, "2023-01-02"),
+            create(80, Expense, "2023-01-03"),
+            create(50, Expense, "2023-01-05"),
+            create(10, Expense, "2023-01-07"),
+            create(100, Income, "2023-01-08"),
+        ]);
+        let low_balance = log.timeline_data().low_balance(50);
+        let at = |summary: Option<DateSummary>| summary.map(|s| (s.period.start(), s.balance));
+        assert_eq!(
+            at(low_balance.below_threshold),
+            Some((date("2023-01-03"), 20))
+        );
+        assert_eq!(at(low_balance.below_zero), Some((date("2023-01-05"), -30)));
+        assert_eq!(at(low_balance.minimum), Some((date("2023-01-07"), -40)));
+    }
+}
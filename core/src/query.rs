@@ -0,0 +1,237 @@
+use crate::app_state::{Date, Dollars, TransactionKind, TransactionRecord};
+
+/// What to order a list of transactions by. Ties keep the order the
+/// transactions were entered in.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum SortKey {
+    /// the order the transactions were entered in
+    #[default]
+    Entered,
+    Date,
+    Value,
+    Kind,
+}
+
+impl SortKey {
+    pub const ALL: [Self; 4] = [Self::Entered, Self::Date, Self::Value, Self::Kind];
+
+    /// the name used in form values
+    pub fn key(&self) -> &'static str {
+        match self {
+            SortKey::Entered => "entered",
+            SortKey::Date => "date",
+            SortKey::Value => "value",
+            SortKey::Kind => "kind",
+        }
+    }
+}
+
+impl std::fmt::Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Entered => write!(f, "Entered"),
+            SortKey::Date => write!(f, "Date"),
+            SortKey::Value => write!(f, "Value"),
+            SortKey::Kind => write!(f, "Kind"),
+        }
+    }
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|k| k.key() == s).ok_or(())
+    }
+}
+
+/// Which transactions to list and in what order. Each filter left as
+/// `None` lets every transaction through.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TransactionQuery {
+    pub kind: Option<TransactionKind>,
+    /// the first date to list
+    pub from: Option<Date>,
+    /// the last date to list
+    pub to: Option<Date>,
+    /// the smallest value to list, inclusive
+    pub min_value: Option<Dollars>,
+    /// the largest value to list, inclusive
+    pub max_value: Option<Dollars>,
+    /// the category to list; an empty one lists the uncategorized
+    pub category: Option<String>,
+    /// `Some(true)` to list only planned transactions, `Some(false)` only
+    /// actual ones
+    pub planned: Option<bool>,
+    /// text to find in the payee, memo, reference or category, ignoring
+    /// case; empty to list everything
+    pub search: String,
+    pub sort: SortKey,
+    pub descending: bool,
+}
+
+impl TransactionQuery {
+    pub fn matches(&self, record: &TransactionRecord) -> bool {
+        let t = &record.transaction;
+        self.kind.is_none_or(|kind| t.kind == kind)
+            && self.from.is_none_or(|from| t.date >= from)
+            && self.to.is_none_or(|to| t.date <= to)
+            && self.min_value.is_none_or(|min| t.value >= min)
+            && self.max_value.is_none_or(|max| t.value <= max)
+            && self.category.as_ref().is_none_or(|c| &t.category == c)
+            && self.planned.is_none_or(|planned| t.planned == planned)
+            && self.matches_search(record)
+    }
+
+    fn matches_search(&self, record: &TransactionRecord) -> bool {
+        let search = self.search.trim().to_lowercase();
+        let t = &record.transaction;
+        search.is_empty()
+            || [&t.payee, &t.memo, &t.category]
+                .into_iter()
+                .chain(&t.reference)
+                .any(|field| field.to_lowercase().contains(&search))
+    }
+
+    /// the records that match, sorted
+    pub fn apply<'a>(
+        &self,
+        records: impl IntoIterator<Item = &'a TransactionRecord>,
+    ) -> Vec<&'a TransactionRecord> {
+        let mut matching: Vec<_> = records.into_iter().filter(|r| self.matches(r)).collect();
+        let kind_order = |kind: TransactionKind| match kind {
+            TransactionKind::Income => 0,
+            TransactionKind::Expense => 1,
+        };
+        // sorted before reversing, so that a descending list is the
+        // ascending one backwards, ties included
+        match self.sort {
+            SortKey::Entered => {}
+            SortKey::Date => matching.sort_by_key(|r| r.transaction.date),
+            SortKey::Value => matching.sort_by_key(|r| r.transaction.value),
+            SortKey::Kind => matching.sort_by_key(|r| kind_order(r.transaction.kind)),
+        }
+        if self.descending {
+            matching.reverse();
+        }
+        matching
+    }
+}
+
+/// The number of transactions in a list and what they add up to.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Totals {
+    pub count: usize,
+    pub income: i64,
+    pub expenses: i64,
+}
+
+impl Totals {
+    pub fn of<'a>(records: impl IntoIterator<Item = &'a TransactionRecord>) -> Self {
+        records.into_iter().fold(Self::default(), |mut totals, r| {
+            totals.count += 1;
+            match r.transaction.kind {
+                TransactionKind::Income => totals.income += r.transaction.value as i64,
+                TransactionKind::Expense => totals.expenses += r.transaction.value as i64,
+            }
+            totals
+        })
+    }
+
+    pub fn net(&self) -> i64 {
+        self.income - self.expenses
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SortKey, Totals, TransactionQuery};
+    use crate::app_state::{Dollars, Transaction, TransactionKind, TransactionRecord};
+    use crate::test_util::date;
+
+    fn records() -> Vec<TransactionRecord> {
+        use TransactionKind::*;
+        let record = |id, value: Dollars, kind, d, payee: &str, category: &str| TransactionRecord {
+            transaction: Transaction {
+                payee: payee.to_owned(),
+                category: category.to_owned(),
+                ..Transaction::new(value, kind, date(d))
+            },
+            id,
+            status: Default::default(),
+            fulfilled_by: None,
+        };
+        vec![
+            record(0, 50, Expense, "2023-01-03", "Grocery store", "Groceries"),
+            record(1, 200, Income, "2023-01-01", "Employer", ""),
+            record(2, 20, Expense, "2023-01-02", "Corner shop", "Groceries"),
+            record(3, 50, Income, "2023-01-05", "", ""),
+        ]
+    }
+
+    fn ids(records: Vec<&TransactionRecord>) -> Vec<u16> {
+        records.iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn test_sort() {
+        let records = records();
+        let sorted = |sort, descending| {
+            ids(TransactionQuery {
+                sort,
+                descending,
+                ..Default::default()
+            }
+            .apply(&records))
+        };
+        assert_eq!(sorted(SortKey::Entered, false), [0, 1, 2, 3]);
+        assert_eq!(sorted(SortKey::Date, false), [1, 2, 0, 3]);
+        assert_eq!(sorted(SortKey::Value, false), [2, 0, 3, 1]);
+        assert_eq!(sorted(SortKey::Value, true), [1, 3, 0, 2]);
+        assert_eq!(sorted(SortKey::Kind, false), [1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn test_filter_and_totals() {
+        let records = records();
+        let query = TransactionQuery {
+            from: Some(date("2023-01-02")),
+            to: Some(date("2023-01-05")),
+            min_value: Some(30),
+            ..Default::default()
+        };
+        let matching = query.apply(&records);
+        assert_eq!(
+            Totals::of(matching.iter().copied()),
+            Totals {
+                count: 2,
+                income: 50,
+                expenses: 50,
+            }
+        );
+        assert_eq!(ids(matching), [0, 3]);
+        let expenses = TransactionQuery {
+            kind: Some(TransactionKind::Expense),
+            max_value: Some(20),
+            ..Default::default()
+        };
+        assert_eq!(ids(expenses.apply(&records)), [2]);
+    }
+
+    #[test]
+    fn test_category_and_search() {
+        let records = records();
+        let category = |category: &str| TransactionQuery {
+            category: Some(category.to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(ids(category("Groceries").apply(&records)), [0, 2]);
+        assert_eq!(ids(category("").apply(&records)), [1, 3]);
+        let search = |search: &str| TransactionQuery {
+            search: search.to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(ids(search(" employer ").apply(&records)), [1]);
+        assert_eq!(ids(search("groc").apply(&records)), [0, 2]);
+        assert_eq!(ids(search("").apply(&records)), [0, 1, 2, 3]);
+    }
+}
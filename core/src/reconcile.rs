@@ -0,0 +1,107 @@
+use crate::app_state::{Date, Dollars, Log, TransactionKind};
+use serde::{Deserialize, Serialize};
+
+/// How far a transaction has been checked against the bank.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    #[default]
+    Uncleared,
+    /// ticked off against a statement not yet reconciled
+    Cleared,
+    /// part of a finished reconciliation, and so can no longer be changed
+    /// or deleted
+    Reconciled,
+}
+
+impl std::fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TransactionStatus::*;
+        match self {
+            Uncleared => write!(f, "Uncleared"),
+            Cleared => write!(f, "Cleared"),
+            Reconciled => write!(f, "Reconciled"),
+        }
+    }
+}
+
+/// A bank statement: the balance the bank gives at the end of
+/// `statement_date`. Appending one to the log reconciles every cleared
+/// transaction dated on or before it.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Reconciliation {
+    pub statement_date: Date,
+    pub statement_balance: Dollars,
+}
+
+impl Log {
+    /// the balance of the cleared and reconciled transactions dated on or
+    /// before `date`, which is what the bank should agree with
+    pub fn cleared_balance(&self, date: Date) -> i64 {
+        self.cleared_transactions()
+            .filter(|t| t.date <= date)
+            .map(|t| match t.kind {
+                TransactionKind::Income => t.value as i64,
+                TransactionKind::Expense => -(t.value as i64),
+            })
+            .sum()
+    }
+
+    /// what the statement says less what the cleared transactions add up
+    /// to; the statement can be reconciled once this is zero
+    pub fn unreconciled_difference(&self, statement: &Reconciliation) -> i64 {
+        statement.statement_balance as i64 - self.cleared_balance(statement.statement_date)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Reconciliation, TransactionStatus};
+    use crate::app_state::{Entry, Log, TransactionKind};
+    use crate::test_util::{create, date};
+
+    fn statuses(log: &Log) -> Vec<TransactionStatus> {
+        log.transaction_records()
+            .iter()
+            .map(|tr| tr.status)
+            .collect()
+    }
+
+    #[test]
+    fn test_reconcile() {
+        use TransactionKind::*;
+        use TransactionStatus::*;
+        let mut log = Log::from(vec![
+            create(100, Income, "2023-01-01"),
+            create(30, Expense, "2023-01-05"),
+            create(20, Expense, "2023-01-20"),
+            create(5, Expense, "2023-02-02"),
+        ]);
+        let statement = Reconciliation {
+            statement_date: date("2023-01-31"),
+            statement_balance: 50,
+        };
+        assert_eq!(log.unreconciled_difference(&statement), 50);
+        log.append(Entry::SetCleared(0, true)).unwrap();
+        log.append(Entry::SetCleared(1, true)).unwrap();
+        log.append(Entry::SetCleared(3, true)).unwrap();
+        assert_eq!(log.unreconciled_difference(&statement), -20);
+        log.append(Entry::SetCleared(2, true)).unwrap();
+        assert_eq!(log.unreconciled_difference(&statement), 0);
+        log.append(Entry::Reconcile(statement.clone())).unwrap();
+        assert_eq!(
+            statuses(&log),
+            [Reconciled, Reconciled, Reconciled, Cleared]
+        );
+        assert_eq!(log.reconciliations(), [statement]);
+
+        // reconciled transactions are locked
+        log.append(Entry::SetCleared(0, false)).unwrap();
+        log.append(Entry::Delete(1)).unwrap();
+        log.append(Entry::SetCleared(3, false)).unwrap();
+        assert_eq!(
+            statuses(&log),
+            [Reconciled, Reconciled, Reconciled, Uncleared]
+        );
+        assert_eq!(log.cleared_balance(date("2023-02-28")), 50);
+    }
+}
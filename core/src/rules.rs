@@ -0,0 +1,230 @@
+use crate::app_state::{Transaction, TransactionKind, TransactionRecord};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+pub type RuleId = u16;
+
+/// Categorizes transactions by payee: a transaction whose payee contains
+/// `payee_contains`, ignoring case, is put in `category` and, if `kind` is
+/// given, made that kind.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    pub payee_contains: String,
+    pub category: String,
+    pub kind: Option<TransactionKind>,
+}
+
+impl Rule {
+    pub fn matches(&self, payee: &str) -> bool {
+        !self.payee_contains.is_empty()
+            && payee
+                .to_lowercase()
+                .contains(&self.payee_contains.to_lowercase())
+    }
+
+    /// `t` as this rule leaves it, or `None` if the rule would not change
+    /// it. A transaction that already has a category is left alone.
+    pub fn apply(&self, t: &Transaction) -> Option<Transaction> {
+        if !t.category.is_empty() || !self.matches(&t.payee) {
+            return None;
+        }
+        let mut applied = t.clone();
+        applied.category = self.category.clone();
+        if let Some(kind) = self.kind {
+            applied.kind = kind;
+        }
+        Some(applied)
+    }
+
+    /// the existing transactions this rule would change, each with what it
+    /// would change it to, had it been in place when they were entered
+    pub fn preview<'a>(
+        &self,
+        records: impl IntoIterator<Item = &'a TransactionRecord>,
+    ) -> Vec<(&'a TransactionRecord, Transaction)> {
+        records
+            .into_iter()
+            .filter_map(|r| Some((r, self.apply(&r.transaction)?)))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RuleRecord {
+    pub rule: Rule,
+    pub id: RuleId,
+}
+
+/// `t` with the first of `rules` that changes it applied, as done when a
+/// transaction is entered. There is no import of transactions yet; when
+/// there is, each imported transaction should go through here as well.
+pub fn categorize(rules: &[RuleRecord], t: Transaction) -> Transaction {
+    rules.iter().find_map(|r| r.rule.apply(&t)).unwrap_or(t)
+}
+
+/// every payee of `records`, sorted and without duplicates, to offer
+/// while one is typed
+pub fn payees(records: &[TransactionRecord]) -> Vec<String> {
+    records
+        .iter()
+        .map(|r| &r.transaction.payee)
+        .filter(|payee| !payee.is_empty())
+        .sorted()
+        .dedup()
+        .cloned()
+        .collect()
+}
+
+/// every category of `records` and `rules`, sorted and without duplicates
+pub fn categories(records: &[TransactionRecord], rules: &[RuleRecord]) -> Vec<String> {
+    records
+        .iter()
+        .map(|r| &r.transaction.category)
+        .chain(rules.iter().map(|r| &r.rule.category))
+        .filter(|category| !category.is_empty())
+        .sorted()
+        .dedup()
+        .cloned()
+        .collect()
+}
+
+/// A category, and maybe a kind, to offer for a transaction with a given
+/// payee.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Suggestion {
+    pub category: String,
+    pub kind: Option<TransactionKind>,
+}
+
+impl Suggestion {
+    /// what the first matching rule says, or failing that, what the latest
+    /// categorized transaction with the same payee was
+    pub fn for_payee(
+        payee: &str,
+        rules: &[RuleRecord],
+        records: &[TransactionRecord],
+    ) -> Option<Self> {
+        let payee = payee.trim();
+        if payee.is_empty() {
+            return None;
+        }
+        let latest = records
+            .iter()
+            .rev()
+            .map(|r| &r.transaction)
+            .find(|t| !t.category.is_empty() && t.payee.eq_ignore_ascii_case(payee));
+        match rules.iter().find(|r| r.rule.matches(payee)) {
+            Some(r) => Some(Self {
+                category: r.rule.category.clone(),
+                kind: r.rule.kind.or(latest.map(|t| t.kind)),
+            }),
+            None => latest.map(|t| Self {
+                category: t.category.clone(),
+                kind: Some(t.kind),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{categories, categorize, payees, Rule, RuleRecord, Suggestion};
+    use crate::app_state::{Transaction, TransactionKind, TransactionRecord};
+
+    fn transaction(payee: &str, category: &str, kind: TransactionKind) -> Transaction {
+        Transaction {
+            payee: payee.to_owned(),
+            category: category.to_owned(),
+            ..Transaction::new(10, kind, "2023-01-01".parse().unwrap())
+        }
+    }
+
+    fn records() -> Vec<TransactionRecord> {
+        use TransactionKind::*;
+        vec![
+            (0, transaction("NETFLIX.COM", "", Income)).into(),
+            (1, transaction("Grocery store", "Groceries", Expense)).into(),
+            (2, transaction("Netflix", "Entertainment", Expense)).into(),
+            (3, transaction("Employer", "", Income)).into(),
+            (4, transaction("grocery store", "Food", Expense)).into(),
+        ]
+    }
+
+    fn rules() -> Vec<RuleRecord> {
+        vec![RuleRecord {
+            rule: Rule {
+                payee_contains: "netflix".to_owned(),
+                category: "Subscriptions".to_owned(),
+                kind: Some(TransactionKind::Expense),
+            },
+            id: 0,
+        }]
+    }
+
+    #[test]
+    fn test_categorize() {
+        use TransactionKind::*;
+        let rules = rules();
+        assert_eq!(
+            categorize(&rules, transaction("Netflix Inc", "", Income)),
+            transaction("Netflix Inc", "Subscriptions", Expense)
+        );
+        assert_eq!(
+            categorize(&rules, transaction("Netflix Inc", "Fun", Income)),
+            transaction("Netflix Inc", "Fun", Income)
+        );
+        assert_eq!(
+            categorize(&rules, transaction("Landlord", "", Expense)),
+            transaction("Landlord", "", Expense)
+        );
+    }
+
+    #[test]
+    fn test_preview() {
+        let records = records();
+        let preview = rules()[0].rule.preview(&records);
+        assert_eq!(
+            preview,
+            [(
+                &records[0],
+                transaction("NETFLIX.COM", "Subscriptions", TransactionKind::Expense)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let records = records();
+        let rules = rules();
+        assert_eq!(
+            payees(&records),
+            [
+                "Employer",
+                "Grocery store",
+                "NETFLIX.COM",
+                "Netflix",
+                "grocery store"
+            ]
+        );
+        assert_eq!(
+            categories(&records, &rules),
+            ["Entertainment", "Food", "Groceries", "Subscriptions"]
+        );
+        assert_eq!(
+            Suggestion::for_payee("Grocery Store", &rules, &records),
+            Some(Suggestion {
+                category: "Food".to_owned(),
+                kind: Some(TransactionKind::Expense),
+            })
+        );
+        assert_eq!(
+            Suggestion::for_payee("netflix", &rules, &records),
+            Some(Suggestion {
+                category: "Subscriptions".to_owned(),
+                kind: Some(TransactionKind::Expense),
+            })
+        );
+        assert_eq!(Suggestion::for_payee("Employer", &rules, &records), None);
+        assert_eq!(Suggestion::for_payee(" ", &rules, &records), None);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::app_state::{AppendError, Log};
+
+impl Log {
+    /// the log as it would be with the named scenario's hypothetical
+    /// entries appended. The scenario is replayed on top of the log as it
+    /// is now, so it follows whatever has happened since it was forked.
+    /// Its transactions, schedules and rules are numbered down from the
+    /// largest id, so their ids don't change as the real log grows; it
+    /// cannot be replayed once the log's ids have grown to meet them.
+    pub fn scenario(&self, name: &str) -> Option<Result<Log, AppendError>> {
+        let entries = self.scenario_entries(name)?;
+        let mut log = self.branch_off();
+        Some(
+            entries
+                .into_iter()
+                .try_for_each(|entry| log.append(entry))
+                .map(|()| log),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app_state::{AppendError, Entry, Log, TransactionId, TransactionKind};
+    use crate::test_util::{balance, create, range};
+
+    fn branch(name: &str, entry: Entry) -> Entry {
+        Entry::Branch(name.to_owned(), Box::new(entry))
+    }
+
+    #[test]
+    fn test_scenario() {
+        use TransactionKind::*;
+        let mut log = Log::from(vec![
+            Entry::SetDate(range("2023-01-01", "2023-03-31")),
+            create(1000, Income, "2023-01-01"),
+            create(200, Expense, "2023-01-15"),
+            Entry::Fork("car".to_owned()),
+            branch("car", create(500, Expense, "2023-03-01")),
+            branch("car", Entry::Delete(1)),
+            // entries for a scenario that doesn't exist are ignored
+            branch("boat", create(900, Expense, "2023-03-01")),
+        ]);
+        assert_eq!(balance(&log), 800);
+        assert_eq!(log.transaction_records().len(), 2);
+        assert_eq!(log.scenario_names(), ["car"]);
+        assert_eq!(balance(&log.scenario("car").unwrap().unwrap()), 500);
+        assert!(log.scenario("boat").is_none());
+
+        // forking again keeps the scenario's entries
+        log.append(Entry::Fork("car".to_owned())).unwrap();
+        assert_eq!(log.scenario_entries("car").unwrap().len(), 2);
+
+        // the scenario follows the log
+        log.append(create(100, Income, "2023-02-01")).unwrap();
+        assert_eq!(balance(&log.scenario("car").unwrap().unwrap()), 600);
+
+        log.append(Entry::DropScenario("car".to_owned())).unwrap();
+        assert!(log.scenario_names().is_empty());
+        assert_eq!(balance(&log), 900);
+    }
+
+    #[test]
+    fn test_scenario_ids_are_stable() {
+        use TransactionKind::*;
+        let hypothetical = TransactionId::MAX;
+        let mut log = Log::from(vec![
+            create(1000, Income, "2023-01-01"),
+            Entry::Fork("trip".to_owned()),
+            branch("trip", create(300, Expense, "2023-01-10")),
+            branch("trip", create(50, Expense, "2023-01-11")),
+        ]);
+        let ids = |log: &Log| {
+            log.transaction_records()
+                .iter()
+                .map(|tr| tr.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(&log.scenario("trip").unwrap().unwrap()),
+            [0, hypothetical - 1, hypothetical]
+        );
+        log.append(branch("trip", Entry::Delete(hypothetical - 1)))
+            .unwrap();
+        log.append(create(200, Expense, "2023-01-05")).unwrap();
+        // the real transaction takes the next real id, and the delete
+        // still drops the hypothetical one it was made against
+        let trip = log.scenario("trip").unwrap().unwrap();
+        assert_eq!(ids(&trip), [0, 1, hypothetical]);
+        assert_eq!(balance(&trip), 1000 - 300 - 200);
+    }
+
+    #[test]
+    fn test_scenario_ids_meet_log_ids() {
+        use TransactionKind::*;
+        let exhausted = AppendError::IdsExhausted("transaction");
+        // the log's ids stop two short of the top
+        let mut log = Log::from(vec![
+            create(1, Income, "2023-01-01");
+            TransactionId::MAX as usize - 1
+        ]);
+        log.append(Entry::Fork("near".to_owned())).unwrap();
+        log.append(branch("near", create(1, Expense, "2023-01-02")))
+            .unwrap();
+        assert!(log.scenario("near").unwrap().is_ok());
+
+        // the next id of each is now taken by the other
+        log.append(create(1, Income, "2023-01-02")).unwrap();
+        let near = log.scenario("near").unwrap().unwrap();
+        let mut more = near.clone();
+        assert_eq!(
+            more.append(create(1, Expense, "2023-01-03")),
+            Err(exhausted.clone())
+        );
+        assert_eq!(more, near);
+
+        // a scenario the log has grown into can't be replayed
+        log.append(create(1, Income, "2023-01-03")).unwrap();
+        assert_eq!(log.scenario("near"), Some(Err(exhausted.clone())));
+        assert_eq!(log.append(create(1, Income, "2023-01-04")), Err(exhausted));
+        assert_eq!(
+            log.transaction_records().len(),
+            TransactionId::MAX as usize + 1
+        );
+    }
+}
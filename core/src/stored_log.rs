@@ -0,0 +1,138 @@
+use crate::app_state::Entry;
+use crate::vault::{Sealed, Vault, VaultError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum StoredLogError {
+    Yaml(String),
+    Vault(VaultError),
+}
+
+impl std::fmt::Display for StoredLogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use StoredLogError::*;
+        match self {
+            Yaml(e) => write!(f, "yaml: {e}"),
+            Vault(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for StoredLogError {}
+
+impl From<VaultError> for StoredLogError {
+    fn from(value: VaultError) -> Self {
+        Self::Vault(value)
+    }
+}
+
+impl From<serde_yaml::Error> for StoredLogError {
+    fn from(value: serde_yaml::Error) -> Self {
+        Self::Yaml(value.to_string())
+    }
+}
+
+/// The document written to localStorage and to exported files. The
+/// serialized `Vec<Entry>` is either stored as is or sealed with a
+/// passphrase-derived key.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum StoredLog {
+    Plain(Vec<Entry>),
+    Encrypted(Sealed),
+}
+
+impl StoredLog {
+    pub fn new(entries: Vec<Entry>, vault: Option<&Vault>) -> Result<Self, StoredLogError> {
+        match vault {
+            None => Ok(Self::Plain(entries)),
+            Some(vault) => {
+                let plaintext = serde_yaml::to_string(&entries)?;
+                Ok(Self::Encrypted(vault.seal(plaintext.as_bytes())?))
+            }
+        }
+    }
+
+    pub fn from_yaml(s: &str) -> Result<Self, StoredLogError> {
+        Ok(serde_yaml::from_str(s)?)
+    }
+
+    pub fn to_yaml(&self) -> Result<String, StoredLogError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+}
+
+/// decrypt `sealed` and parse the entries it contains
+pub fn unlock(passphrase: &str, sealed: &Sealed) -> Result<(Vault, Vec<Entry>), StoredLogError> {
+    let (vault, plaintext) = Vault::unlock(passphrase, sealed)?;
+    let entries = serde_yaml::from_slice(&plaintext)?;
+    Ok((vault, entries))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{unlock, StoredLog, StoredLogError};
+    use crate::app_state::{Entry, Transaction, TransactionKind};
+    use crate::date_range::DateRange;
+    use crate::vault::{Vault, VaultError};
+
+    fn entries() -> Vec<Entry> {
+        vec![Entry::Create(Transaction {
+            payee: "Employer".to_owned(),
+            memo: "January pay".to_owned(),
+            reference: Some("INV-7".to_owned()),
+            ..Transaction::new(100, TransactionKind::Income, "2023-01-02".parse().unwrap())
+        })]
+    }
+
+    #[test]
+    fn test_plain_round_trip() {
+        let stored_log = StoredLog::new(entries(), None).unwrap();
+        let yaml = stored_log.to_yaml().unwrap();
+        assert_eq!(
+            StoredLog::from_yaml(&yaml).unwrap(),
+            StoredLog::Plain(entries())
+        );
+    }
+
+    #[test]
+    fn test_plain_without_details() {
+        let yaml = "!Plain\n- !Create\n  value: 100\n  kind: Income\n  date: 2023-01-02\n";
+        assert_eq!(
+            StoredLog::from_yaml(yaml).unwrap(),
+            StoredLog::Plain(vec![Entry::Create(Transaction::new(
+                100,
+                TransactionKind::Income,
+                "2023-01-02".parse().unwrap()
+            ))])
+        );
+    }
+
+    #[test]
+    fn test_legacy_inverted_date_range() {
+        let yaml = "!Plain\n- !SetDate\n  start: 2023-03-31\n  end: 2023-01-01\n";
+        assert_eq!(
+            StoredLog::from_yaml(yaml).unwrap(),
+            StoredLog::Plain(vec![Entry::SetDate(
+                DateRange::new("2023-01-01".parse().unwrap(), "2023-03-31".parse().unwrap())
+                    .unwrap()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_encrypted_round_trip() {
+        let vault = Vault::create("passphrase").unwrap();
+        let stored_log = StoredLog::new(entries(), Some(&vault)).unwrap();
+        let yaml = stored_log.to_yaml().unwrap();
+        assert!(!yaml.contains("Income"));
+        let sealed = match StoredLog::from_yaml(&yaml).unwrap() {
+            StoredLog::Encrypted(sealed) => sealed,
+            StoredLog::Plain(_) => panic!("expected encrypted log"),
+        };
+        assert_eq!(unlock("passphrase", &sealed).unwrap(), (vault, entries()));
+        assert_eq!(
+            unlock("wrong", &sealed).unwrap_err(),
+            StoredLogError::Vault(VaultError::Unauthenticated)
+        );
+    }
+}
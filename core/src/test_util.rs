@@ -0,0 +1,47 @@
+//! Fixtures shared by the unit tests.
+
+use crate::app_state::{Date, Dollars, Entry, Log, Transaction, TransactionId, TransactionKind};
+use crate::date_range::DateRange;
+
+pub fn date(s: &str) -> Date {
+    s.parse().unwrap()
+}
+
+pub fn range(start: &str, end: &str) -> DateRange {
+    DateRange::new(date(start), date(end)).unwrap()
+}
+
+/// a `Create` of a transaction with no details beyond its value, kind and
+/// date
+pub fn create(value: Dollars, kind: TransactionKind, d: &str) -> Entry {
+    Entry::Create(Transaction::new(value, kind, date(d)))
+}
+
+/// an expense in `category`, with no other details
+pub fn expense(value: Dollars, d: &str, category: &str) -> Transaction {
+    Transaction {
+        category: category.to_owned(),
+        ..Transaction::new(value, TransactionKind::Expense, date(d))
+    }
+}
+
+/// a `Create` of an expense planned in `category`
+pub fn planned(value: Dollars, d: &str, category: &str) -> Entry {
+    Entry::Create(Transaction {
+        planned: true,
+        ..expense(value, d, category)
+    })
+}
+
+/// a `Create` of the actual expense that fulfils `plan`
+pub fn fulfilling(value: Dollars, d: &str, category: &str, plan: TransactionId) -> Entry {
+    Entry::Create(Transaction {
+        fulfils: Some(plan),
+        ..expense(value, d, category)
+    })
+}
+
+/// the balance at the end of the log's date range
+pub fn balance(log: &Log) -> Dollars {
+    log.timeline_data().iter().last().unwrap().balance
+}
@@ -0,0 +1,128 @@
+use crate::app_state::{Log, TimelineData, TransactionKind};
+use crate::date_range::DateRange;
+use std::collections::BTreeMap;
+
+/// What was planned for a category over a period, against what actually
+/// happened. Amounts are income less expenses.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Variance {
+    pub period: DateRange,
+    /// empty for uncategorized transactions
+    pub category: String,
+    pub planned: i64,
+    pub actual: i64,
+}
+
+impl Variance {
+    /// how much better, or with a negative variance worse, things went
+    /// than planned
+    pub fn variance(&self) -> i64 {
+        self.actual - self.planned
+    }
+}
+
+impl Log {
+    /// planned against actual amounts for each period of `data` and each
+    /// category with transactions in it. Fulfilled plans are counted, as
+    /// they are what was planned.
+    pub fn variance(&self, data: &TimelineData) -> Vec<Variance> {
+        let periods: Vec<&DateRange> = data.iter().map(|s| &s.period).collect();
+        // each transaction goes in the period containing it, found by
+        // binary search as the periods are in order
+        let mut totals = BTreeMap::<(usize, &str), (i64, i64)>::new();
+        for (_, t) in self.transactions() {
+            let n = periods.partition_point(|period| period.end() < t.date);
+            if !periods
+                .get(n)
+                .is_some_and(|period| period.contains(&t.date))
+            {
+                continue;
+            }
+            let value = match t.kind {
+                TransactionKind::Income => t.value as i64,
+                TransactionKind::Expense => -(t.value as i64),
+            };
+            let (planned, actual) = totals.entry((n, &t.category)).or_default();
+            if t.planned {
+                *planned += value;
+            } else {
+                *actual += value;
+            }
+        }
+        totals
+            .into_iter()
+            .map(|((n, category), (planned, actual))| Variance {
+                period: periods[n].clone(),
+                category: category.to_owned(),
+                planned,
+                actual,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Variance;
+    use crate::app_state::{Entry, Log};
+    use crate::granularity::Granularity;
+    use crate::test_util::{balance, date, expense, fulfilling, planned, range};
+
+    #[test]
+    fn test_fulfil_plan() {
+        let mut log = Log::from(vec![
+            Entry::SetDate(range("2023-01-01", "2023-01-31")),
+            planned(100, "2023-01-10", "Rent"),
+        ]);
+        assert_eq!(balance(&log), -100);
+        assert!(log.is_open_plan(0));
+
+        // the actual transaction replaces the plan in the balance
+        log.append(fulfilling(90, "2023-01-12", "Rent", 0)).unwrap();
+        assert_eq!(balance(&log), -90);
+        assert!(!log.is_open_plan(0));
+        assert_eq!(log.transaction_records()[0].fulfilled_by, Some(1));
+
+        // a plan is only fulfilled once
+        log.append(fulfilling(5, "2023-01-13", "Rent", 0)).unwrap();
+        assert_eq!(balance(&log), -95);
+        log.append(Entry::Delete(2)).unwrap();
+
+        // deleting the actual transaction reopens the plan
+        log.append(Entry::Delete(1)).unwrap();
+        assert_eq!(balance(&log), -100);
+        assert!(log.is_open_plan(0));
+        log.append(fulfilling(90, "2023-01-12", "Rent", 0)).unwrap();
+        log.append(Entry::Delete(0)).unwrap();
+        assert_eq!(balance(&log), -90);
+    }
+
+    #[test]
+    fn test_variance() {
+        let log = Log::from(vec![
+            planned(100, "2023-01-10", "Rent"),
+            fulfilling(90, "2023-01-12", "Rent", 0),
+            planned(50, "2023-01-20", "Food"),
+            Entry::Create(expense(20, "2023-02-03", "Food")),
+            Entry::Create(expense(30, "2023-02-05", "Food")),
+        ]);
+        let months = range("2023-01-01", "2023-02-28");
+        let month = |m: &str| Granularity::Month.period_containing(date(m));
+        let variance = |m, category: &str, planned, actual| Variance {
+            period: month(m),
+            category: category.to_owned(),
+            planned,
+            actual,
+        };
+        let report = log.variance(&log.timeline_data_by(&months, Granularity::Month));
+        assert_eq!(
+            report,
+            [
+                variance("2023-01-01", "Food", -50, 0),
+                variance("2023-01-01", "Rent", -100, -90),
+                variance("2023-02-01", "Food", 0, -50),
+            ]
+        );
+        assert_eq!(report[1].variance(), 10);
+    }
+}
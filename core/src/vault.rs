@@ -0,0 +1,159 @@
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use serde::{Deserialize, Serialize};
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum VaultError {
+    /// the passphrase is wrong or the ciphertext has been tampered with
+    Unauthenticated,
+    /// the sealed document could not be decoded
+    Malformed(String),
+    KeyDerivation(String),
+    Random(String),
+}
+
+impl std::fmt::Display for VaultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use VaultError::*;
+        match self {
+            Unauthenticated => write!(f, "wrong passphrase or corrupted data"),
+            Malformed(e) => write!(f, "malformed sealed data: {e}"),
+            KeyDerivation(e) => write!(f, "could not derive key: {e}"),
+            Random(e) => write!(f, "could not generate random bytes: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for VaultError {}
+
+/// Ciphertext together with everything besides the passphrase that is
+/// needed to decrypt it. Binary fields are base64 encoded so the
+/// document stays readable as YAML.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Sealed {
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// A key derived from a passphrase with Argon2id. Deriving is slow on
+/// purpose, so a `Vault` is created once per session and reused to seal
+/// every save.
+#[derive(Clone, PartialEq)]
+pub struct Vault {
+    salt: [u8; SALT_LEN],
+    key: [u8; 32],
+}
+
+impl std::fmt::Debug for Vault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Vault").finish_non_exhaustive()
+    }
+}
+
+impl Vault {
+    /// derive a key from `passphrase` using a fresh random salt
+    pub fn create(passphrase: &str) -> Result<Self, VaultError> {
+        let mut salt = [0u8; SALT_LEN];
+        getrandom::getrandom(&mut salt).map_err(|e| VaultError::Random(e.to_string()))?;
+        Self::derive(passphrase, salt)
+    }
+
+    /// derive the key `sealed` was encrypted with and check it by
+    /// decrypting. Returns the vault and the plaintext.
+    pub fn unlock(passphrase: &str, sealed: &Sealed) -> Result<(Self, Vec<u8>), VaultError> {
+        let salt = decode::<SALT_LEN>(&sealed.salt)?;
+        let vault = Self::derive(passphrase, salt)?;
+        let plaintext = vault.open(sealed)?;
+        Ok((vault, plaintext))
+    }
+
+    fn derive(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<Self, VaultError> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| VaultError::KeyDerivation(e.to_string()))?;
+        Ok(Self { salt, key })
+    }
+
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Sealed, VaultError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut nonce).map_err(|e| VaultError::Random(e.to_string()))?;
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| VaultError::Unauthenticated)?;
+        Ok(Sealed {
+            salt: STANDARD.encode(self.salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+    }
+
+    pub fn open(&self, sealed: &Sealed) -> Result<Vec<u8>, VaultError> {
+        let nonce = decode::<NONCE_LEN>(&sealed.nonce)?;
+        let ciphertext = STANDARD
+            .decode(&sealed.ciphertext)
+            .map_err(|e| VaultError::Malformed(e.to_string()))?;
+        self.cipher()
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| VaultError::Unauthenticated)
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(Key::from_slice(&self.key))
+    }
+}
+
+fn decode<const N: usize>(s: &str) -> Result<[u8; N], VaultError> {
+    STANDARD
+        .decode(s)
+        .map_err(|e| VaultError::Malformed(e.to_string()))?
+        .try_into()
+        .map_err(|_| VaultError::Malformed(format!("expected {N} bytes")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Vault, VaultError};
+
+    #[test]
+    fn test_round_trip() {
+        let vault = Vault::create("correct horse").unwrap();
+        let sealed = vault.seal(b"battery staple").unwrap();
+        let (unlocked, plaintext) = Vault::unlock("correct horse", &sealed).unwrap();
+        assert_eq!(plaintext, b"battery staple");
+        assert_eq!(unlocked, vault);
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let sealed = Vault::create("correct horse")
+            .unwrap()
+            .seal(b"battery staple")
+            .unwrap();
+        assert_eq!(
+            Vault::unlock("incorrect horse", &sealed).unwrap_err(),
+            VaultError::Unauthenticated
+        );
+    }
+
+    #[test]
+    fn test_tampered_ciphertext() {
+        let vault = Vault::create("correct horse").unwrap();
+        let mut sealed = vault.seal(b"battery staple").unwrap();
+        let other = vault.seal(b"battery stapler").unwrap();
+        sealed.ciphertext = other.ciphertext;
+        assert_eq!(
+            vault.open(&sealed).unwrap_err(),
+            VaultError::Unauthenticated
+        );
+    }
+}
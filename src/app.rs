@@ -1,132 +1,447 @@
-use crate::app_state::*;
+use crate::charts::Overlay;
 use crate::components::*;
+use crate::storage;
 use chrono::Duration;
+use financial_planner_core::stored_log::StoredLog;
+use financial_planner_core::vault::{Sealed, Vault};
+use financial_planner_core::*;
 use yew::prelude::*;
 
 use TransactionKind::{Expense, Income};
 
+/// days of history the forecast baseline averages over
+const FORECAST_TRAILING_DAYS: u32 = 90;
+
 fn today_plus(days: i64) -> Date {
     chrono::Local::now().date_naive() + Duration::days(days)
 }
 
+fn sample_entries() -> Vec<Entry> {
+    use Entry::{Create, SetDate};
+    vec![
+        SetDate(DateRange::new(today_plus(0), today_plus(30)).unwrap()),
+        Create(Transaction {
+            payee: "Employer".to_owned(),
+            category: "Salary".to_owned(),
+            ..Transaction::new(100, Income, today_plus(1))
+        }),
+        Create(Transaction {
+            payee: "Freelance client".to_owned(),
+            ..Transaction::new(100, Income, today_plus(2))
+        }),
+        Create(Transaction {
+            payee: "Grocery store".to_owned(),
+            category: "Groceries".to_owned(),
+            ..Transaction::new(100, Expense, today_plus(3))
+        }),
+        Create(Transaction {
+            payee: "Landlord".to_owned(),
+            memo: "Rent".to_owned(),
+            category: "Housing".to_owned(),
+            ..Transaction::new(500, Expense, today_plus(4))
+        }),
+        Create(Transaction {
+            payee: "Power company".to_owned(),
+            category: "Utilities".to_owned(),
+            planned: true,
+            ..Transaction::new(80, Expense, today_plus(10))
+        }),
+    ]
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Session {
+    Locked(Sealed),
+    Unlocked {
+        entries: Vec<Entry>,
+        vault: Option<Vault>,
+    },
+    /// the stored log couldn't be loaded; nothing is saved until the user
+    /// chooses to start over, so that it can still be exported
+    Unreadable {
+        error: String,
+        raw: Option<String>,
+    },
+}
+
+fn initial_session() -> Session {
+    match storage::load() {
+        Ok(Some(StoredLog::Encrypted(sealed))) => Session::Locked(sealed),
+        Ok(Some(StoredLog::Plain(entries))) => Session::Unlocked {
+            entries,
+            vault: None,
+        },
+        Ok(None) => Session::Unlocked {
+            entries: sample_entries(),
+            vault: None,
+        },
+        Err(e) => {
+            gloo_console::log!(format!("load log: {e}"));
+            Session::Unreadable {
+                error: e.to_string(),
+                raw: storage::load_raw().ok().flatten(),
+            }
+        }
+    }
+}
+
 #[function_component(App)]
 pub fn app() -> Html {
-    let log = use_reducer(|| {
-        use Entry::{Create, SetDate};
-        Log::from(vec![
-            SetDate((today_plus(0), today_plus(30)).into()),
-            Create(Transaction {
-                value: 100,
-                kind: Income,
-                date: today_plus(1),
-            }),
-            Create(Transaction {
-                value: 100,
-                kind: Income,
-                date: today_plus(2),
-            }),
-            Create(Transaction {
-                value: 100,
-                kind: Expense,
-                date: today_plus(3),
-            }),
-            Create(Transaction {
-                value: 500,
-                kind: Expense,
-                date: today_plus(4),
-            }),
-        ])
-    });
-    let start_date = use_state(|| today_plus(0));
-    let end_date = use_state(|| today_plus(30));
+    let session = use_state(initial_session);
 
-    let set_start_date = {
-        let start_date = start_date.clone();
-        let end_date = end_date.clone();
+    let on_unlock = {
+        let session = session.clone();
+        move |(vault, entries)| {
+            session.set(Session::Unlocked {
+                entries,
+                vault: Some(vault),
+            })
+        }
+    };
+
+    let start_over = {
+        let session = session.clone();
+        move |_| {
+            session.set(Session::Unlocked {
+                entries: sample_entries(),
+                vault: None,
+            })
+        }
+    };
+
+    html! {
+    <main
+        class={classes!("w3-container", "w3-content")}
+        style={"max-width: 1200px;"}
+    >
+        <div class={classes!("w3-container", "w3-panel", "w3-orange")}>
+            <p><b>{concat!{
+                "This app is for demonstration purposes only. Unless a passphrase is set, ",
+                "any information entered into this app is stored unencrypted in the browser ",
+                "and should be considered effectively public information.",
+            }}</b></p>
+        </div>
+        {match (*session).clone() {
+            Session::Locked(sealed) => html!{
+                <UnlockScreen {sealed} {on_unlock} />
+            },
+            Session::Unlocked { entries, vault } => html!{
+                <Planner {entries} {vault} />
+            },
+            Session::Unreadable { error, raw } => html!{
+                <LoadErrorScreen {error} {raw} {start_over} />
+            },
+        }}
+    </main>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct PlannerProps {
+    entries: Vec<Entry>,
+    vault: Option<Vault>,
+}
+
+#[function_component(Planner)]
+fn planner(props: &PlannerProps) -> Html {
+    let log = use_reducer(|| Log::from(props.entries.clone()));
+    let vault = use_state(|| props.vault.clone());
+    let start_date = use_state(|| log.date_range().start());
+    let end_date = use_state(|| log.date_range().end());
+
+    use_effect_with_deps(
+        |(log, vault)| {
+            if let Err(e) = StoredLog::new(log.entries(), vault.as_ref())
+                .map_err(storage::StorageError::from)
+                .and_then(|s| storage::save(&s))
+            {
+                gloo_console::log!(format!("save log: {e}"));
+            }
+            || {}
+        },
+        ((*log).clone(), (*vault).clone()),
+    );
+
+    let set_passphrase = {
+        let vault = vault.clone();
+        move |passphrase: String| match Vault::create(&passphrase) {
+            Ok(v) => vault.set(Some(v)),
+            Err(e) => gloo_console::log!(format!("set passphrase: {e}")),
+        }
+    };
+
+    let remove_passphrase = {
+        let vault = vault.clone();
+        move |_| vault.set(None)
+    };
+
+    let export = {
         let log = log.clone();
-        move |date_string: String| match date_string.parse::<Date>() {
-            Ok(date) => {
-                start_date.set(date);
-                log.dispatch(Entry::SetDate((date, *end_date).into()));
+        let vault = vault.clone();
+        move |_| {
+            if let Err(e) = StoredLog::new(log.entries(), vault.as_ref())
+                .map_err(storage::StorageError::from)
+                .and_then(|s| storage::export_file("financial-planner-log.yaml", &s))
+            {
+                gloo_console::log!(format!("export log: {e}"));
             }
-            Err(e) => gloo_console::log!(format!("start date: {e:?}")),
         }
     };
 
-    let set_end_date = {
+    let set_date_range = {
         let start_date = start_date.clone();
         let end_date = end_date.clone();
         let log = log.clone();
-        move |date_string: String| match date_string.parse::<Date>() {
-            Ok(date) => {
-                end_date.set(date);
-                log.dispatch(Entry::SetDate((*start_date, date).into()));
+        move |date_range: DateRange| {
+            start_date.set(date_range.start());
+            end_date.set(date_range.end());
+            log.dispatch(Entry::SetDate(date_range));
+        }
+    };
+
+    let granularity = use_state_eq(Granularity::default);
+    let set_granularity = {
+        let granularity = granularity.clone();
+        move |g: Granularity| granularity.set(g)
+    };
+
+    let forecast = use_state_eq(|| false);
+    let set_forecast = {
+        let forecast = forecast.clone();
+        move |f: bool| forecast.set(f)
+    };
+
+    let todays_dollars = use_state_eq(|| false);
+    let set_todays_dollars = {
+        let todays_dollars = todays_dollars.clone();
+        move |t: bool| todays_dollars.set(t)
+    };
+
+    let threshold = use_state_eq(|| 0);
+    let set_threshold = {
+        let threshold = threshold.clone();
+        move |t: Dollars| threshold.set(t)
+    };
+
+    // kept the same across renders, so that list items whose transaction
+    // hasn't changed aren't rendered again
+    let delete_transaction = use_callback(
+        |id, dispatcher| dispatcher.dispatch(Entry::Delete(id)),
+        log.dispatcher(),
+    );
+
+    let schedule = {
+        let log = log.clone();
+        move |s: Schedule| log.dispatch(Entry::Schedule(s))
+    };
+
+    let unschedule = {
+        let log = log.clone();
+        move |id| log.dispatch(Entry::Unschedule(id))
+    };
+
+    let set_interest = {
+        let log = log.clone();
+        move |interest| log.dispatch(Entry::SetInterest(interest))
+    };
+
+    let set_inflation = {
+        let log = log.clone();
+        move |inflation| log.dispatch(Entry::SetInflation(inflation))
+    };
+
+    let add_loan = {
+        let log = log.clone();
+        move |transactions: Vec<Transaction>| {
+            for t in transactions {
+                log.dispatch(Entry::Create(t));
             }
-            Err(e) => gloo_console::log!(format!("end date: {e:?}")),
         }
     };
 
-    let delete_transaction = {
+    let timeline_data = if *forecast {
+        log.forecast_data_by(&log.date_range(), *granularity, FORECAST_TRAILING_DAYS)
+    } else {
+        log.timeline_data_by(&log.date_range(), *granularity)
+    };
+    // warnings are found day by day whatever the chart's granularity
+    let low_balance = if *forecast {
+        log.forecast_data_by(&log.date_range(), Granularity::Day, FORECAST_TRAILING_DAYS)
+    } else {
+        log.timeline_data()
+    }
+    .low_balance(*threshold);
+    let variance = log.variance(&timeline_data);
+    // the chart can be deflated to today's dollars once there's a rate
+    let deflate = |data: TimelineData| match log.inflation() {
+        Some(inflation) if *todays_dollars => data.in_dollars_of(inflation, today_plus(0)),
+        _ => data,
+    };
+    let timeline_data = deflate(timeline_data);
+
+    let scenario = use_state_eq(|| None::<String>);
+    let select_scenario = {
+        let scenario = scenario.clone();
+        move |name: Option<String>| scenario.set(name)
+    };
+    let scenario_log = scenario
+        .as_ref()
+        .and_then(|name| match log.scenario(name)? {
+            Ok(scenario_log) => Some(scenario_log),
+            Err(e) => {
+                gloo_console::log!(format!("scenario {name}: {e}"));
+                None
+            }
+        });
+    // the scenario is drawn over the same periods as the log
+    let overlay = scenario
+        .as_ref()
+        .zip(scenario_log.as_ref())
+        .map(|(name, scenario_log)| Overlay {
+            label: name.clone(),
+            data: deflate(if *forecast {
+                scenario_log.forecast_data_by(
+                    &log.date_range(),
+                    *granularity,
+                    FORECAST_TRAILING_DAYS,
+                )
+            } else {
+                scenario_log.timeline_data_by(&log.date_range(), *granularity)
+            }),
+        });
+    let scenario_entries = scenario
+        .as_ref()
+        .and_then(|name| log.scenario_entries(name))
+        .unwrap_or_default();
+
+    let fork = {
         let log = log.clone();
-        move |id| log.dispatch(Entry::Delete(id))
+        let scenario = scenario.clone();
+        move |name: String| {
+            log.dispatch(Entry::Fork(name.clone()));
+            scenario.set(Some(name));
+        }
     };
 
-    let report_income = {
+    let drop_scenario = {
         let log = log.clone();
-        move |(date, value)| {
-            log.dispatch(Entry::Create(Transaction {
-                value,
-                kind: Income,
-                date,
-            }));
+        let scenario = scenario.clone();
+        move |name: String| {
+            if scenario.as_ref() == Some(&name) {
+                scenario.set(None);
+            }
+            log.dispatch(Entry::DropScenario(name));
         }
     };
 
-    let report_expense = {
+    let branch = {
         let log = log.clone();
-        move |(date, value)| {
-            log.dispatch(Entry::Create(Transaction {
-                value,
-                kind: Expense,
-                date,
-            }));
+        let scenario = scenario.clone();
+        move |entry: Entry| {
+            if let Some(name) = (*scenario).clone() {
+                log.dispatch(Entry::Branch(name, Box::new(entry)));
+            }
         }
     };
 
+    // rules fill in what the form left blank
+    let report_transaction = {
+        let log = log.clone();
+        move |transaction| {
+            let transaction = categorize(&log.rule_records(), transaction);
+            log.dispatch(Entry::Create(transaction))
+        }
+    };
+
+    let statement = use_state_eq(|| None::<Reconciliation>);
+    let set_statement = {
+        let statement = statement.clone();
+        move |s: Option<Reconciliation>| statement.set(s)
+    };
+    let difference = statement.as_ref().map(|s| log.unreconciled_difference(s));
+
+    let set_cleared = {
+        let log = log.clone();
+        move |(id, cleared)| log.dispatch(Entry::SetCleared(id, cleared))
+    };
+
+    let reconcile = {
+        let log = log.clone();
+        move |r: Reconciliation| log.dispatch(Entry::Reconcile(r))
+    };
+
+    let add_rule = {
+        let log = log.clone();
+        move |r: Rule| log.dispatch(Entry::AddRule(r))
+    };
+
+    let remove_rule = {
+        let log = log.clone();
+        move |id| log.dispatch(Entry::RemoveRule(id))
+    };
+
     html! {
-    <main
-        class={classes!("w3-container", "w3-content")}
-        style={"max-width: 1200px;"}
-    >
-        <div class={classes!("w3-container", "w3-panel", "w3-orange")}>
-            <p><b>{concat!{
-                "This app is for demonstration purposes only. It is not intended to secure ",
-                "private information. Any information entered into this app should be ",
-                "considered effectively public information.",
-            }}</b></p>
+    <div class={classes!("w3-row")}>
+        <div class={classes!("w3-col", "l9", "m9", "s9")}>
+            <MainAppArea
+                transaction_records={log.transaction_records()}
+                {delete_transaction}
+                {report_transaction}
+                start_date={*start_date}
+                end_date={*end_date}
+                schedule_records={log.schedule_records()}
+                {schedule}
+                {unschedule}
+                {add_loan}
+                interest={log.interest()}
+                {set_interest}
+                inflation={log.inflation().cloned()}
+                {set_inflation}
+                rule_records={log.rule_records()}
+                {add_rule}
+                {remove_rule}
+                reconciliations={log.reconciliations()}
+                statement={(*statement).clone()}
+                {set_statement}
+                {difference}
+                {set_cleared}
+                {reconcile}
+                {timeline_data}
+                {variance}
+                {overlay}
+                scenario_names={log.scenario_names()}
+                scenario={(*scenario).clone()}
+                {select_scenario}
+                {fork}
+                {drop_scenario}
+                {scenario_log}
+                {scenario_entries}
+                {branch}
+                {low_balance}
+                threshold={*threshold}
+                {set_threshold}
+                {set_date_range}
+                granularity={*granularity}
+                {set_granularity}
+                forecast={*forecast}
+                {set_forecast}
+                todays_dollars={log.inflation().map(|_| *todays_dollars)}
+                {set_todays_dollars}
+            />
         </div>
-        <div class={classes!("w3-row")}>
-            <div class={classes!("w3-col", "l9", "m9", "s9")}>
-                <MainAppArea
-                    transaction_records={log.transaction_records()}
-                    {delete_transaction}
-                    {report_income}
-                    {report_expense}
-                    start_date={*start_date}
-                    end_date={*end_date}
-                    timeline_data={log.timeline_data()}
-                    {set_start_date}
-                    {set_end_date}
-                />
-            </div>
-            <div
-                id={"debug-window"}
-                class={classes!("w3-col", "l3", "m3", "s3")}
-            >
-                <DebugWindow log={log.entries()} />
-            </div>
+        <div
+            id={"debug-window"}
+            class={classes!("w3-col", "l3", "m3", "s3")}
+        >
+            <StorageControls
+                encrypted={vault.is_some()}
+                {set_passphrase}
+                {remove_passphrase}
+                {export}
+            />
+            <DebugWindow log={log.entries()} />
         </div>
-    </main>
+    </div>
     }
 }
 
@@ -134,43 +449,166 @@ pub fn app() -> Html {
 struct MainAppAreaProps {
     transaction_records: Vec<TransactionRecord>,
     delete_transaction: Callback<TransactionId>,
-    report_income: Callback<(Date, Dollars)>,
-    report_expense: Callback<(Date, Dollars)>,
+    report_transaction: Callback<Transaction>,
+    low_balance: LowBalance,
+    threshold: Dollars,
+    set_threshold: Callback<Dollars>,
+    schedule_records: Vec<ScheduleRecord>,
+    schedule: Callback<Schedule>,
+    unschedule: Callback<ScheduleId>,
+    add_loan: Callback<Vec<Transaction>>,
+    interest: Option<Interest>,
+    set_interest: Callback<Option<Interest>>,
+    inflation: Option<Inflation>,
+    set_inflation: Callback<Option<Inflation>>,
+    rule_records: Vec<RuleRecord>,
+    add_rule: Callback<Rule>,
+    remove_rule: Callback<RuleId>,
+    reconciliations: Vec<Reconciliation>,
+    statement: Option<Reconciliation>,
+    set_statement: Callback<Option<Reconciliation>>,
+    difference: Option<i64>,
+    set_cleared: Callback<(TransactionId, bool)>,
+    reconcile: Callback<Reconciliation>,
     timeline_data: TimelineData,
+    variance: Vec<Variance>,
+    overlay: Option<Overlay>,
+    scenario_names: Vec<String>,
+    scenario: Option<String>,
+    select_scenario: Callback<Option<String>>,
+    fork: Callback<String>,
+    drop_scenario: Callback<String>,
+    scenario_log: Option<Log>,
+    scenario_entries: Vec<Entry>,
+    branch: Callback<Entry>,
     start_date: Date,
     end_date: Date,
-    set_start_date: Callback<String>,
-    set_end_date: Callback<String>,
+    set_date_range: Callback<DateRange>,
+    granularity: Granularity,
+    set_granularity: Callback<Granularity>,
+    forecast: bool,
+    set_forecast: Callback<bool>,
+    todays_dollars: Option<bool>,
+    set_todays_dollars: Callback<bool>,
 }
 
 #[function_component(MainAppArea)]
 fn main_app_area(props: &MainAppAreaProps) -> Html {
+    // the period picked on the chart, if any, to list transactions for
+    let period_handle = use_state_eq(|| None::<DateRange>);
+    let select_period = {
+        let period_handle = period_handle.clone();
+        Callback::from(move |period: DateRange| period_handle.set(Some(period)))
+    };
+    let clear_period = {
+        let period_handle = period_handle.clone();
+        Callback::from(move |_| period_handle.set(None))
+    };
     html! {
     <>
+    <BalanceWarnings
+        low_balance={props.low_balance.clone()}
+        threshold={props.threshold}
+        set_threshold={props.set_threshold.clone()}
+    />
     <TransactionsList
         title={"Transactions List"}
         data={props.transaction_records.clone()}
         delete_transaction={props.delete_transaction.clone()}
+        period={(*period_handle).clone()}
+        {clear_period}
     />
     <div class={classes!("w3-container", "w3-content")}>
         <h3>{"Transaction Forms"}</h3>
         <TransactionForm
             title={"Income Form"}
-            submit={props.report_income.clone()}
+            kind={Income}
+            submit={props.report_transaction.clone()}
+            transaction_records={props.transaction_records.clone()}
+            rule_records={props.rule_records.clone()}
         />
         <TransactionForm
             title={"Expense Form"}
-            submit={props.report_expense.clone()}
+            kind={Expense}
+            submit={props.report_transaction.clone()}
+            transaction_records={props.transaction_records.clone()}
+            rule_records={props.rule_records.clone()}
+        />
+        <ScheduleForm
+            title={"Recurring Transaction Form"}
+            submit={props.schedule.clone()}
         />
     </div>
+    <SchedulesList
+        title={"Recurring Transactions"}
+        data={props.schedule_records.clone()}
+        unschedule={props.unschedule.clone()}
+    />
+    <LoanForm
+        title={"Loan Form"}
+        submit={props.add_loan.clone()}
+    />
+    <RuleForm
+        title={"Categorization Rule Form"}
+        submit={props.add_rule.clone()}
+        transaction_records={props.transaction_records.clone()}
+    />
+    <RulesList
+        title={"Categorization Rules"}
+        data={props.rule_records.clone()}
+        remove_rule={props.remove_rule.clone()}
+    />
+    <ReconciliationForm
+        title={"Reconciliation"}
+        transaction_records={props.transaction_records.clone()}
+        reconciliations={props.reconciliations.clone()}
+        statement={props.statement.clone()}
+        set_statement={props.set_statement.clone()}
+        difference={props.difference}
+        set_cleared={props.set_cleared.clone()}
+        reconcile={props.reconcile.clone()}
+    />
     <Timeline
         title={"Timeline"}
         canvas_id={"my_canvas"}
         data={props.timeline_data.clone()}
-        start_date={props.start_date.to_string()}
-        end_date={props.end_date.to_string()}
-        set_start_date={props.set_start_date.clone()}
-        set_end_date={props.set_end_date.clone()}
+        start_date={props.start_date}
+        end_date={props.end_date}
+        set_date_range={props.set_date_range.clone()}
+        granularity={props.granularity}
+        set_granularity={props.set_granularity.clone()}
+        forecast={props.forecast}
+        set_forecast={props.set_forecast.clone()}
+        todays_dollars={props.todays_dollars}
+        set_todays_dollars={props.set_todays_dollars.clone()}
+        threshold={props.threshold}
+        overlay={props.overlay.clone()}
+        {select_period}
+    />
+    <InterestForm
+        title={"Interest"}
+        interest={props.interest}
+        set_interest={props.set_interest.clone()}
+    />
+    <InflationForm
+        title={"Inflation"}
+        inflation={props.inflation.clone()}
+        set_inflation={props.set_inflation.clone()}
+    />
+    <ScenarioPanel
+        title={"What-if Scenarios"}
+        names={props.scenario_names.clone()}
+        selected={props.scenario.clone()}
+        select={props.select_scenario.clone()}
+        fork={props.fork.clone()}
+        drop_scenario={props.drop_scenario.clone()}
+        log={props.scenario_log.clone()}
+        entries={props.scenario_entries.clone()}
+        branch={props.branch.clone()}
+    />
+    <VarianceReport
+        title={"Planned vs Actual"}
+        data={props.variance.clone()}
     />
     </>
     }
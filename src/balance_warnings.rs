@@ -0,0 +1,75 @@
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct BalanceWarningsProps {
+    pub low_balance: LowBalance,
+    pub threshold: Dollars,
+    pub set_threshold: Callback<Dollars>,
+}
+
+fn when(summary: &DateSummary) -> String {
+    let forecast = if summary.forecast { " (forecast)" } else { "" };
+    format!("{}{forecast}", summary.period.start())
+}
+
+/// The first overdraft, the first dip below the threshold and the lowest
+/// balance in the current range.
+#[function_component(BalanceWarnings)]
+pub fn balance_warnings(props: &BalanceWarningsProps) -> Html {
+    let on_threshold_change = {
+        let set_threshold = props.set_threshold.clone();
+        move |e: Event| {
+            if let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            {
+                match input.value().parse::<Dollars>() {
+                    Ok(threshold) => set_threshold.emit(threshold),
+                    Err(e) => gloo_console::log!(format!("threshold: {e:?}")),
+                }
+            }
+        }
+    };
+
+    let LowBalance {
+        below_zero,
+        below_threshold,
+        minimum,
+    } = &props.low_balance;
+    // a threshold of zero is the overdraft itself, and dipping below any
+    // other is only news if it happens on a different day
+    let below_threshold = below_threshold.as_ref().filter(|s| {
+        props.threshold != 0 && below_zero.as_ref().is_none_or(|z| s.period != z.period)
+    });
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        {for below_zero.iter().map(|s| html!{
+            <div class={classes!("w3-panel", "w3-pale-red", "w3-leftbar", "w3-border-red")}>
+                <p>{format!("Overdrawn from {}: balance {}", when(s), s.balance)}</p>
+            </div>
+        })}
+        {for below_threshold.iter().map(|s| html!{
+            <div class={classes!("w3-panel", "w3-pale-yellow", "w3-leftbar", "w3-border-yellow")}>
+                <p>{format!(
+                    "Below {} from {}: balance {}",
+                    props.threshold,
+                    when(s),
+                    s.balance
+                )}</p>
+            </div>
+        })}
+        {for minimum.iter().map(|s| html!{
+            <p>{format!("Lowest balance: {} on {}", s.balance, when(s))}</p>
+        })}
+        <label for="threshold">{"Warn below: "}</label>
+        <input onchange={on_threshold_change}
+            id="threshold"
+            type="number"
+            value={props.threshold.to_string()}
+        />
+    </section>
+    }
+}
@@ -0,0 +1,797 @@
+//! Drawing the timeline charts on any plotters backend: a canvas in the
+//! page, or SVG and PNG files.
+
+use std::error::Error;
+use std::ops::Range;
+
+use chrono::Datelike;
+use financial_planner_core::*;
+use plotters::coord::ranged1d::{DefaultFormatting, KeyPointHint, ReversibleRanged};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters_canvas::CanvasBackend;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlCanvasElement;
+
+/// the size in pixels of exported charts
+pub const EXPORT_SIZE: (u32, u32) = (1200, 800);
+
+/// the room each tick label on the x axis needs, in pixels
+const TICK_WIDTH: u32 = 70;
+
+/// the margin around charts and the width of their y axis labels, in
+/// pixels
+const MARGIN: u32 = 5;
+const Y_LABEL_AREA: u32 = 50;
+
+/// Finds the index of the period drawn at a point, given in pixels of the
+/// backend the chart was drawn on.
+pub type PeriodAt = Box<dyn Fn((i32, i32)) -> Option<usize>>;
+
+/// The x axis of a chart of periods: period `n` is drawn from `n` to
+/// `n + 1`, with ticks in the middle of the periods `ticks`.
+#[derive(Debug, Clone)]
+struct PeriodAxis {
+    len: usize,
+    ticks: Vec<usize>,
+}
+
+impl PeriodAxis {
+    /// the axis of `periods` for a chart `width` pixels wide, and the
+    /// tick labels by period
+    fn new(periods: &[DateRange], width: u32) -> (Self, Vec<String>) {
+        let ticks = tick_labels(periods, width.saturating_sub(Y_LABEL_AREA + 2 * MARGIN));
+        let mut labels = vec![String::new(); periods.len()];
+        let axis = Self {
+            len: periods.len(),
+            ticks: ticks.iter().map(|(n, _)| *n).collect(),
+        };
+        for (n, label) in ticks {
+            labels[n] = label;
+        }
+        (axis, labels)
+    }
+
+    fn linear(&self) -> RangedCoordf64 {
+        (0.0..self.len as f64).into()
+    }
+}
+
+impl Ranged for PeriodAxis {
+    type FormatOption = DefaultFormatting;
+    type ValueType = f64;
+
+    fn map(&self, value: &f64, limit: (i32, i32)) -> i32 {
+        self.linear().map(value, limit)
+    }
+
+    fn key_points<Hint: KeyPointHint>(&self, hint: Hint) -> Vec<f64> {
+        if hint.weight().allow_light_points() {
+            vec![]
+        } else {
+            self.ticks.iter().map(|n| *n as f64 + 0.5).collect()
+        }
+    }
+
+    fn range(&self) -> Range<f64> {
+        0.0..self.len as f64
+    }
+}
+
+fn period_at<Y: Ranged>(coord: &Cartesian2d<PeriodAxis, Y>) -> PeriodAt {
+    let len = coord.x_spec().len;
+    let x_spec = coord.x_spec().linear();
+    let x_pixels = coord.get_x_axis_pixel_range();
+    let y_pixels = coord.get_y_axis_pixel_range();
+    let (top, bottom) = (
+        y_pixels.start.min(y_pixels.end),
+        y_pixels.start.max(y_pixels.end),
+    );
+    Box::new(move |(x, y)| {
+        if !x_pixels.contains(&x) || y < top || y > bottom {
+            return None;
+        }
+        let v = x_spec.unmap(x, (x_pixels.start, x_pixels.end))?;
+        (0.0..len as f64).contains(&v).then_some(v as usize)
+    })
+}
+
+/// the space either side of a period's bars, as a fraction of its width
+const BAR_GAP: f64 = 0.1;
+
+/// the left and right of bar `k` of the `count` drawn side by side in
+/// period `n`
+fn bar_span(n: usize, k: usize, count: usize) -> (f64, f64) {
+    let width = (1.0 - 2.0 * BAR_GAP) / count as f64;
+    let left = n as f64 + BAR_GAP + k as f64 * width;
+    (left, left + width)
+}
+
+fn bar<Y>(
+    (n, k, count): (usize, usize, usize),
+    base: Y,
+    value: Y,
+    style: ShapeStyle,
+) -> Rectangle<(f64, Y)> {
+    let (left, right) = bar_span(n, k, count);
+    Rectangle::new([(left, base), (right, value)], style)
+}
+
+/// an entry in a chart's legend
+fn legend_box(style: ShapeStyle) -> impl Fn((i32, i32)) -> Rectangle<(i32, i32)> {
+    move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], style)
+}
+
+/// A second balance series drawn over a chart, such as a what-if
+/// scenario's, over the same periods as the chart's data.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Overlay {
+    pub label: String,
+    pub data: TimelineData,
+}
+
+impl Overlay {
+    const COLOR: RGBColor = RGBColor(140, 60, 200);
+
+    /// the overlay's balance in each period of `data`, by index
+    fn balances<'a>(
+        &'a self,
+        data: &'a TimelineData,
+    ) -> impl Iterator<Item = (usize, Dollars)> + 'a {
+        self.data
+            .iter()
+            .zip(data.iter())
+            .enumerate()
+            .filter(|(_, (o, s))| o.period == s.period)
+            .map(|(n, (o, _))| (n, o.balance))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ChartKind {
+    /// income, expenses and non-negative balance as bars
+    Histogram,
+    /// the signed balance as a line over income and expense bars
+    Balance,
+}
+
+impl ChartKind {
+    /// the name used in exported filenames
+    pub fn key(&self) -> &'static str {
+        match self {
+            ChartKind::Histogram => "histogram",
+            ChartKind::Balance => "balance",
+        }
+    }
+
+    pub fn draw<DB: DrawingBackend>(
+        &self,
+        root: &DrawingArea<DB, Shift>,
+        data: &TimelineData,
+        threshold: Dollars,
+        overlay: Option<&Overlay>,
+    ) -> Result<PeriodAt, Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        match self {
+            ChartKind::Histogram => draw_timeline(root, data, threshold, overlay),
+            ChartKind::Balance => draw_balance_chart(root, data, threshold, overlay),
+        }
+    }
+
+    /// the chart as an SVG document of `size` pixels
+    pub fn svg(
+        &self,
+        data: &TimelineData,
+        threshold: Dollars,
+        overlay: Option<&Overlay>,
+        size: (u32, u32),
+    ) -> Result<String, Box<dyn Error>> {
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, size).into_drawing_area();
+            let _period_at = self.draw(&root, data, threshold, overlay)?;
+        }
+        Ok(svg)
+    }
+
+    /// the chart as a PNG `data:` URL of `size` pixels. plotters can only
+    /// draw text into a `BitMapBackend` natively, so in the browser the
+    /// chart is drawn on a detached canvas which then encodes it.
+    pub fn png_data_url(
+        &self,
+        data: &TimelineData,
+        threshold: Dollars,
+        overlay: Option<&Overlay>,
+        size: (u32, u32),
+    ) -> Result<String, Box<dyn Error>> {
+        let canvas: HtmlCanvasElement = web_sys::window()
+            .and_then(|w| w.document())
+            .ok_or("expecting a document on window")?
+            .create_element("canvas")
+            .map_err(|e| format!("{e:?}"))?
+            .dyn_into()
+            .map_err(|_| "canvas element to be HtmlCanvasElement")?;
+        canvas.set_width(size.0);
+        canvas.set_height(size.1);
+        let backend =
+            CanvasBackend::with_canvas_object(canvas.clone()).ok_or("cannot draw on canvas")?;
+        let _period_at = self.draw(&backend.into_drawing_area(), data, threshold, overlay)?;
+        Ok(canvas
+            .to_data_url_with_type("image/png")
+            .map_err(|e| format!("{e:?}"))?)
+    }
+}
+
+pub fn draw_timeline<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    data: &TimelineData,
+    threshold: Dollars,
+    overlay: Option<&Overlay>,
+) -> Result<PeriodAt, Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    if data.len() == 0 {
+        return Ok(Box::new(|_| None));
+    }
+    let periods = data.iter().map(|s| s.period.clone()).collect::<Vec<_>>();
+    root.fill(&WHITE)?;
+
+    let overlay_max = overlay
+        .and_then(|o| o.balances(data).map(|(_, balance)| balance).max())
+        .unwrap_or(0);
+    let max = if let Some(value) = data
+        .iter()
+        .map(|v| v.income.max(v.expenses).max(v.balance).max(overlay_max))
+        .max()
+    {
+        math::round::floor(value as f64 * 1.1, 1).max(100.0) as u32
+    } else {
+        return Ok(Box::new(|_| None));
+    };
+
+    let (x_axis, labels) = PeriodAxis::new(&periods, root.dim_in_pixel().0);
+    let mut chart = ChartBuilder::on(root)
+        .x_label_area_size(35)
+        .y_label_area_size(Y_LABEL_AREA)
+        .margin(MARGIN)
+        .build_cartesian_2d(x_axis, 0u32..max)?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .x_label_formatter(&|v| labels.get(*v as usize).cloned().unwrap_or_default())
+        .y_labels(10)
+        .bold_line_style(WHITE.mix(0.3))
+        .y_desc("Dollars")
+        .x_desc("Date")
+        .axis_desc_style(("sans-serif", 15))
+        .draw()?;
+
+    // the bars of each period side by side, with actuals filled and
+    // forecasts outlined
+    let series = [
+        (
+            "Income",
+            BLUE,
+            Box::new(|s: &DateSummary| s.income) as Box<dyn Fn(&DateSummary) -> Dollars>,
+        ),
+        ("Expenses", RED, Box::new(|s: &DateSummary| s.expenses)),
+        (
+            "Balance",
+            BLACK,
+            Box::new(|s: &DateSummary| s.balance.max(0)),
+        ),
+    ];
+    let style = |color: &RGBColor, forecast: bool| {
+        if forecast {
+            color.mix(0.8).stroke_width(2)
+        } else {
+            color.mix(0.5).filled()
+        }
+    };
+    for (k, (label, color, value)) in series.iter().enumerate() {
+        chart
+            .draw_series(data.iter().enumerate().map(|(n, s)| {
+                bar(
+                    (n, k, series.len()),
+                    0,
+                    value(s) as u32,
+                    style(color, s.forecast),
+                )
+            }))?
+            .label(*label)
+            .legend(legend_box(style(color, false)));
+    }
+    if data.iter().any(|s| s.forecast) {
+        chart
+            .draw_series(std::iter::empty::<Rectangle<(f64, u32)>>())?
+            .label("Forecast")
+            .legend(legend_box(style(&BLACK, true)));
+    }
+
+    // the overlay runs through the middle of the balance bars
+    if let Some(overlay) = overlay {
+        chart
+            .draw_series(LineSeries::new(
+                overlay.balances(data).map(|(n, balance)| {
+                    let (left, right) = bar_span(n, 2, series.len());
+                    ((left + right) / 2.0, balance.max(0) as u32)
+                }),
+                Overlay::COLOR.stroke_width(2),
+            ))?
+            .label(format!("{} balance", overlay.label))
+            .legend(|(x, y)| {
+                PathElement::new([(x, y), (x + 10, y)], Overlay::COLOR.stroke_width(2))
+            });
+    }
+
+    // markers point at the balance bar
+    for (n, balance, label, color) in low_balance_markers(data, threshold) {
+        let (left, right) = bar_span(n, 2, series.len());
+        chart.draw_series(std::iter::once(
+            EmptyElement::at(((left + right) / 2.0, balance.max(0) as u32))
+                + TriangleMarker::new((0, -8), 6, color.filled())
+                + Text::new(label, (8, -16), ("sans-serif", 12).into_font()),
+        ))?;
+    }
+
+    draw_legend(&mut chart)?;
+    root.present()?;
+    Ok(period_at(chart.as_coord_spec()))
+}
+
+pub fn draw_balance_chart<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    data: &TimelineData,
+    threshold: Dollars,
+    overlay: Option<&Overlay>,
+) -> Result<PeriodAt, Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    if data.len() == 0 {
+        return Ok(Box::new(|_| None));
+    }
+    let periods = data.iter().map(|s| s.period.clone()).collect::<Vec<_>>();
+    root.fill(&WHITE)?;
+
+    let (min, max) = data.iter().fold((0, 0), |(min, max), s| {
+        (
+            min.min(s.balance).min(threshold),
+            max.max(s.income).max(s.expenses).max(s.balance),
+        )
+    });
+    let (min, max) = overlay
+        .into_iter()
+        .flat_map(|o| o.balances(data))
+        .fold((min, max), |(min, max), (_, balance)| {
+            (min.min(balance), max.max(balance))
+        });
+    let (min, max) = (min as f64 * 1.1, (max as f64 * 1.1).max(100.0));
+
+    let (x_axis, labels) = PeriodAxis::new(&periods, root.dim_in_pixel().0);
+    let mut chart = ChartBuilder::on(root)
+        .x_label_area_size(35)
+        .y_label_area_size(Y_LABEL_AREA)
+        .margin(MARGIN)
+        .build_cartesian_2d(x_axis, min..max)?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .x_label_formatter(&|v| labels.get(*v as usize).cloned().unwrap_or_default())
+        .y_label_formatter(&|v| format!("{v:.0}"))
+        .y_labels(10)
+        .bold_line_style(WHITE.mix(0.3))
+        .y_desc("Dollars")
+        .x_desc("Date")
+        .axis_desc_style(("sans-serif", 15))
+        .draw()?;
+
+    // shade the periods that end overdrawn
+    let overdrawn = RED.mix(0.1).filled();
+    let shading = chart.draw_series(
+        data.iter()
+            .enumerate()
+            .filter(|(_, s)| s.balance < 0)
+            .map(|(n, _)| Rectangle::new([(n as f64, min), (n as f64 + 1.0, max)], overdrawn)),
+    )?;
+    if data.iter().any(|s| s.balance < 0) {
+        shading.label("Overdrawn").legend(legend_box(overdrawn));
+    }
+
+    for (k, (label, color)) in [("Income", BLUE), ("Expenses", RED)].iter().enumerate() {
+        chart
+            .draw_series(data.iter().enumerate().map(|(n, s)| {
+                let value = [s.income, s.expenses][k] as f64;
+                bar((n, k, 2), 0.0, value, color.mix(0.4).filled())
+            }))?
+            .label(*label)
+            .legend(legend_box(color.mix(0.4).filled()));
+    }
+
+    let last = periods.len() as f64;
+    chart.draw_series(LineSeries::new(
+        [(0.0, 0.0), (last, 0.0)],
+        BLACK.stroke_width(1),
+    ))?;
+    if threshold != 0 {
+        let color = RGBColor(230, 160, 0);
+        chart
+            .draw_series(LineSeries::new(
+                [(0.0, threshold as f64), (last, threshold as f64)],
+                color.stroke_width(1),
+            ))?
+            .label(format!("Threshold ({threshold})"))
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 10, y)], color.stroke_width(1)));
+    }
+
+    let points = data
+        .iter()
+        .enumerate()
+        .map(|(n, s)| ((n as f64 + 0.5, s.balance as f64), s.forecast))
+        .collect::<Vec<_>>();
+    chart.draw_series(AreaSeries::new(
+        points.iter().map(|(point, _)| *point),
+        0.0,
+        BLACK.mix(0.1),
+    ))?;
+    // the forecast continues the line from the last actual point in a
+    // lighter colour
+    let forecast_from = points
+        .iter()
+        .position(|(_, forecast)| *forecast)
+        .unwrap_or(points.len());
+    chart
+        .draw_series(LineSeries::new(
+            points[..forecast_from].iter().map(|(point, _)| *point),
+            BLACK.stroke_width(2),
+        ))?
+        .label("Balance")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 10, y)], BLACK.stroke_width(2)));
+    let forecast = chart.draw_series(LineSeries::new(
+        points[forecast_from.saturating_sub(1)..]
+            .iter()
+            .map(|(point, _)| *point),
+        BLACK.mix(0.4).stroke_width(2),
+    ))?;
+    if forecast_from < points.len() {
+        forecast.label("Forecast balance").legend(|(x, y)| {
+            PathElement::new([(x, y), (x + 10, y)], BLACK.mix(0.4).stroke_width(2))
+        });
+    }
+
+    if let Some(overlay) = overlay {
+        chart
+            .draw_series(LineSeries::new(
+                overlay
+                    .balances(data)
+                    .map(|(n, balance)| (n as f64 + 0.5, balance as f64)),
+                Overlay::COLOR.stroke_width(2),
+            ))?
+            .label(format!("{} balance", overlay.label))
+            .legend(|(x, y)| {
+                PathElement::new([(x, y), (x + 10, y)], Overlay::COLOR.stroke_width(2))
+            });
+    }
+
+    for (n, balance, label, color) in low_balance_markers(data, threshold) {
+        chart.draw_series(std::iter::once(
+            EmptyElement::at((n as f64 + 0.5, balance as f64))
+                + TriangleMarker::new((0, -8), 6, color.filled())
+                + Text::new(label, (8, -16), ("sans-serif", 12).into_font()),
+        ))?;
+    }
+
+    draw_legend(&mut chart)?;
+    root.present()?;
+    Ok(period_at(chart.as_coord_spec()))
+}
+
+fn draw_legend<'a, DB: DrawingBackend + 'a, Y: Ranged>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<PeriodAxis, Y>>,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK.mix(0.3))
+        .label_font(("sans-serif", 12))
+        .draw()?;
+    Ok(())
+}
+
+/// The unit the ticks on a chart's x axis step by.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum TickUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TickUnit {
+    /// dates with the same key are in the same unit
+    fn key(&self, date: Date) -> (i32, u32) {
+        match self {
+            TickUnit::Day => (date.year(), date.ordinal()),
+            TickUnit::Week => {
+                let week = date.iso_week();
+                (week.year(), week.week())
+            }
+            TickUnit::Month => (date.year(), date.month()),
+            TickUnit::Year => (date.year(), 0),
+        }
+    }
+}
+
+/// the periods to put ticks on along an axis `width` pixels long, and
+/// their labels. Ticks go on the periods starting every day or every few
+/// days, weeks, months or years, whichever is the finest that leaves each
+/// label room, and each label only shows the month or year when it
+/// changes from the tick before.
+fn tick_labels(periods: &[DateRange], width: u32) -> Vec<(usize, String)> {
+    let max_ticks = (width / TICK_WIDTH).max(2) as usize;
+    let starts = |unit: TickUnit| {
+        (0..periods.len())
+            .filter(|n| {
+                *n == 0 || unit.key(periods[*n].start()) != unit.key(periods[*n - 1].start())
+            })
+            .collect::<Vec<_>>()
+    };
+    let every = |unit: TickUnit, step: usize| {
+        let ticks: Vec<usize> = starts(unit).into_iter().step_by(step).collect();
+        (unit, ticks)
+    };
+    use TickUnit::*;
+    let (unit, ticks) = [
+        (Day, 1),
+        (Day, 2),
+        (Day, 3),
+        (Week, 1),
+        (Week, 2),
+        (Month, 1),
+        (Month, 2),
+        (Month, 3),
+        (Month, 6),
+    ]
+    .into_iter()
+    .map(|(unit, step)| every(unit, step))
+    .find(|(_, ticks)| ticks.len() <= max_ticks)
+    .unwrap_or_else(|| {
+        let years = starts(Year).len();
+        every(Year, years.div_ceil(max_ticks))
+    });
+    let mut previous: Option<Date> = None;
+    ticks
+        .into_iter()
+        .map(|n| {
+            let date = periods[n].start();
+            let changed = |unit: TickUnit| previous.is_none_or(|p| unit.key(p) != unit.key(date));
+            let format = match unit {
+                Day | Week if changed(Month) => "%b %d",
+                Day | Week => "%d",
+                Month if changed(Year) => "%b %Y",
+                Month => "%b",
+                Year => "%Y",
+            };
+            previous = Some(date);
+            (n, date.format(format).to_string())
+        })
+        .collect()
+}
+
+/// the index and balance of each period to mark on a chart, with its
+/// label and colour. Markers on the same period are merged, keeping the
+/// colour of the most serious.
+fn low_balance_markers(
+    data: &TimelineData,
+    threshold: Dollars,
+) -> Vec<(usize, Dollars, String, RGBColor)> {
+    let low_balance = data.low_balance(threshold);
+    let mut markers: Vec<(usize, Dollars, String, RGBColor)> = Vec::new();
+    for (summary, label, color) in [
+        (low_balance.below_zero, "overdrawn", RED),
+        (
+            low_balance.below_threshold.filter(|_| threshold != 0),
+            "below threshold",
+            RGBColor(230, 160, 0),
+        ),
+        (low_balance.minimum, "lowest", BLACK),
+    ] {
+        let Some(summary) = summary else { continue };
+        let Some(n) = data.iter().position(|s| s.period == summary.period) else {
+            continue;
+        };
+        match markers.iter_mut().find(|marker| marker.0 == n) {
+            Some(marker) => marker.2 = format!("{}, {label}", marker.2),
+            None => markers.push((n, summary.balance, label.to_owned(), color)),
+        }
+    }
+    markers
+}
+
+#[cfg(test)]
+mod test {
+    use super::{tick_labels, ChartKind, Overlay};
+    use financial_planner_core::*;
+    use itertools::Itertools;
+    use plotters::prelude::*;
+
+    const SIZE: (u32, u32) = (400, 300);
+
+    fn log() -> Log {
+        use TransactionKind::*;
+        let date = |s: &str| s.parse::<Date>().unwrap();
+        let transaction = |value, kind, d| Entry::Create(Transaction::new(value, kind, date(d)));
+        Log::from(vec![
+            Entry::SetDate(DateRange::new(date("2023-01-01"), date("2023-01-08")).unwrap()),
+            transaction(100, Income, "2023-01-02"),
+            transaction(150, Expense, "2023-01-04"),
+            transaction(80, Income, "2023-01-07"),
+            Entry::Fork("raise".to_owned()),
+            Entry::Branch(
+                "raise".to_owned(),
+                Box::new(transaction(120, Income, "2023-01-05")),
+            ),
+        ])
+    }
+
+    fn data() -> TimelineData {
+        log().timeline_data()
+    }
+
+    fn overlay() -> Overlay {
+        let log = log();
+        Overlay {
+            label: "raise".to_owned(),
+            data: log
+                .scenario("raise")
+                .unwrap()
+                .unwrap()
+                .timeline_data_in(&log.date_range()),
+        }
+    }
+
+    /// compare the shapes in `svg` with those in the file in
+    /// `src/snapshots`, or overwrite the file if `UPDATE_SNAPSHOTS` is
+    /// set. Text is left out, since where it goes depends on the fonts
+    /// installed, and the shapes are sorted, since plotters draws the bars
+    /// of a histogram in no particular order.
+    fn assert_snapshot(name: &str, svg: &str) {
+        let shapes = |svg: &str| {
+            svg.lines()
+                .filter(|line| line.starts_with('<') && !line.contains("text"))
+                .sorted()
+                .join("\n")
+        };
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/snapshots")
+            .join(format!("{name}.svg"));
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, svg).unwrap();
+        }
+        let expected = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(shapes(svg), shapes(&expected), "{}", path.display());
+    }
+
+    #[test]
+    fn test_histogram_snapshot() {
+        assert_snapshot(
+            "histogram",
+            &ChartKind::Histogram.svg(&data(), 0, None, SIZE).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_overlay_snapshots() {
+        let overlay = overlay();
+        assert_snapshot(
+            "histogram_overlay",
+            &ChartKind::Histogram
+                .svg(&data(), 0, Some(&overlay), SIZE)
+                .unwrap(),
+        );
+        assert_snapshot(
+            "balance_overlay",
+            &ChartKind::Balance
+                .svg(&data(), 20, Some(&overlay), SIZE)
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_balance_chart_snapshot() {
+        assert_snapshot(
+            "balance",
+            &ChartKind::Balance.svg(&data(), 20, None, SIZE).unwrap(),
+        );
+    }
+
+    // the balance goes negative for three days, which are shaded red
+    #[test]
+    fn test_balance_chart_bitmap() {
+        let mut pixels = vec![0; (SIZE.0 * SIZE.1 * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut pixels, SIZE).into_drawing_area();
+            let _period_at = ChartKind::Balance.draw(&root, &data(), 0, None).unwrap();
+        }
+        let shaded = RED.mix(0.1).filled().color.to_rgba();
+        let blended =
+            |c: u8, bg: u8| (c as f64 * shaded.3 + bg as f64 * (1.0 - shaded.3)).round() as u8;
+        let pink = [
+            blended(shaded.0, 255),
+            blended(shaded.1, 255),
+            blended(shaded.2, 255),
+        ];
+        assert!(pixels
+            .chunks(3)
+            .any(|p| p.iter().zip(pink).all(|(a, b)| a.abs_diff(b) <= 1)));
+    }
+
+    // sweeping across the middle of the chart passes over every period in
+    // order, while the margins are over none of them
+    #[test]
+    fn test_period_at() {
+        for kind in [ChartKind::Histogram, ChartKind::Balance] {
+            let mut svg = String::new();
+            let root = SVGBackend::with_string(&mut svg, SIZE).into_drawing_area();
+            let period_at = kind.draw(&root, &data(), 0, None).unwrap();
+            let y = SIZE.1 as i32 / 2;
+            let periods: Vec<usize> = (0..SIZE.0 as i32)
+                .filter_map(|x| period_at((x, y)))
+                .dedup()
+                .collect();
+            assert_eq!(periods, (0..data().len()).collect::<Vec<_>>(), "{kind:?}");
+            assert_eq!(period_at((0, 0)), None);
+            assert_eq!(period_at((SIZE.0 as i32 - 1, SIZE.1 as i32 - 1)), None);
+        }
+    }
+
+    #[test]
+    fn test_tick_labels() {
+        let days = |start: &str, n: u64| {
+            let start = start.parse::<Date>().unwrap();
+            (0..n)
+                .map(|d| {
+                    let day = start + chrono::Days::new(d);
+                    DateRange::new(day, day).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        // room for five labels, or two
+        let labels = |periods: &[DateRange], width| {
+            tick_labels(periods, width)
+                .into_iter()
+                .map(|(n, label)| format!("{n}:{label}"))
+                .join(" ")
+        };
+        assert_eq!(
+            labels(&days("2023-01-30", 4), 350),
+            "0:Jan 30 1:31 2:Feb 01 3:02"
+        );
+        assert_eq!(
+            labels(&days("2023-01-01", 8), 350),
+            "0:Jan 01 2:03 4:05 6:07"
+        );
+        // 2023-01-02 is a Monday
+        assert_eq!(
+            labels(&days("2023-01-01", 21), 350),
+            "0:Jan 01 1:02 8:09 15:16"
+        );
+        assert_eq!(
+            labels(&days("2022-12-01", 90), 350),
+            "0:Dec 2022 31:Jan 2023 62:Feb"
+        );
+        assert_eq!(labels(&days("2020-01-01", 1500), 140), "0:2020 1096:2023");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::app_state::*;
+use financial_planner_core::*;
 use itertools::Itertools;
 use yew::prelude::*;
 
@@ -0,0 +1,133 @@
+use financial_planner_core::*;
+use std::collections::BTreeMap;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct InflationFormProps {
+    pub title: String,
+    pub inflation: Option<Inflation>,
+    pub set_inflation: Callback<Option<Inflation>>,
+}
+
+fn set_from_input(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(input) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+        {
+            handle.set(input.value());
+        }
+    }
+}
+
+/// The inflation rate that indexed schedules grow with, and that the
+/// timeline can deflate by to show today's dollars. Particular years can
+/// be given a rate of their own.
+#[function_component(InflationForm)]
+pub fn inflation_form(props: &InflationFormProps) -> Html {
+    let current = props.inflation.clone();
+    let rate_handle = use_state(|| {
+        current
+            .as_ref()
+            .map(|i| i.rate.to_string())
+            .unwrap_or_default()
+    });
+    let by_year_handle = use_state(|| {
+        current
+            .as_ref()
+            .map(|i| i.by_year.clone())
+            .unwrap_or_default()
+    });
+    let year_handle = use_state(String::default);
+    let year_rate_handle = use_state(String::default);
+
+    let submit = {
+        let set_inflation = props.set_inflation.clone();
+        let rate = (*rate_handle).clone();
+        let by_year = (*by_year_handle).clone();
+        move |_| match rate.trim().parse::<f64>() {
+            Ok(rate) if Inflation::is_valid_rate(rate) => set_inflation.emit(Some(Inflation {
+                rate,
+                by_year: by_year.clone(),
+            })),
+            _ => gloo_console::log!(format!("inflation: invalid rate")),
+        }
+    };
+
+    let add_year = {
+        let by_year_handle = by_year_handle.clone();
+        let year_handle = year_handle.clone();
+        let year_rate_handle = year_rate_handle.clone();
+        move |_| {
+            let year = year_handle.trim().parse::<i32>();
+            let rate = year_rate_handle.trim().parse::<f64>();
+            match (year, rate) {
+                (Ok(year), Ok(rate)) if Inflation::is_valid_rate(rate) => {
+                    let mut by_year: BTreeMap<i32, f64> = (*by_year_handle).clone();
+                    by_year.insert(year, rate);
+                    by_year_handle.set(by_year);
+                    year_handle.set(String::default());
+                    year_rate_handle.set(String::default());
+                }
+                _ => gloo_console::log!(format!("inflation: invalid year or rate")),
+            }
+        }
+    };
+
+    let remove = {
+        let set_inflation = props.set_inflation.clone();
+        move |_| set_inflation.emit(None)
+    };
+
+    html! {
+        <section class={classes!("w3-container", "w3-content")}>
+            <h3>{props.title.clone()}</h3>
+            <p>{match &current {
+                Some(i) if i.by_year.is_empty() => format!("{}% a year.", i.rate),
+                Some(i) => format!("{}% a year, except as below.", i.rate),
+                None => "No inflation.".to_owned(),
+            }}</p>
+            <input onchange={set_from_input(rate_handle.clone())}
+                type="number"
+                step="0.1"
+                placeholder="Yearly rate %"
+                value={(*rate_handle).clone()}
+            />
+            <button onclick={submit}>{"Set"}</button>
+            if current.is_some() {
+                <button onclick={remove}>{"Remove"}</button>
+            }
+            <ul id="inflation-by-year" class={classes!("w3-ul")}>
+                {for by_year_handle.iter().map(|(year, rate)| {
+                    let by_year_handle = by_year_handle.clone();
+                    let year = *year;
+                    let remove_year = move |_| {
+                        let mut by_year = (*by_year_handle).clone();
+                        by_year.remove(&year);
+                        by_year_handle.set(by_year);
+                    };
+                    html!{
+                        <li key={year}>
+                            {format!("{year}: {rate}% ")}
+                            <button onclick={remove_year}>{"Remove"}</button>
+                        </li>
+                    }
+                })}
+            </ul>
+            <input onchange={set_from_input(year_handle.clone())}
+                type="number"
+                placeholder="Year"
+                value={(*year_handle).clone()}
+            />
+            <input onchange={set_from_input(year_rate_handle.clone())}
+                type="number"
+                step="0.1"
+                placeholder="Rate that year %"
+                value={(*year_rate_handle).clone()}
+            />
+            <button onclick={add_year}>{"Add year"}</button>
+        </section>
+    }
+}
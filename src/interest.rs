@@ -0,0 +1,141 @@
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct InterestFormProps {
+    pub title: String,
+    pub interest: Option<Interest>,
+    pub set_interest: Callback<Option<Interest>>,
+}
+
+fn set_from_input(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(input) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+        {
+            handle.set(input.value());
+        }
+    }
+}
+
+fn set_from_select(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(select) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+        {
+            handle.set(select.value());
+        }
+    }
+}
+
+/// The interest the balance earns, or is charged when overdrawn, which
+/// the timeline accrues into the balance.
+#[function_component(InterestForm)]
+pub fn interest_form(props: &InterestFormProps) -> Html {
+    let current = props.interest;
+    let savings_handle = use_state(|| {
+        current
+            .map(|i| i.savings_rate.to_string())
+            .unwrap_or_default()
+    });
+    let credit_handle = use_state(|| {
+        current
+            .map(|i| i.credit_rate.to_string())
+            .unwrap_or_default()
+    });
+    let basis_handle = use_state(|| current.map_or(RateBasis::Apy, |i| i.basis).key().to_owned());
+    let compounding_handle = use_state(|| {
+        current
+            .map_or(Compounding::Monthly, |i| i.compounding)
+            .key()
+            .to_owned()
+    });
+
+    let submit = {
+        let set_interest = props.set_interest.clone();
+        let savings = (*savings_handle).clone();
+        let credit = (*credit_handle).clone();
+        let basis = RateBasis::ALL
+            .into_iter()
+            .find(|b| b.key() == *basis_handle);
+        let compounding = Compounding::ALL
+            .into_iter()
+            .find(|c| c.key() == *compounding_handle);
+        move |_| {
+            // a blank rate is no interest that way
+            let rate = |s: &str| match s.trim() {
+                "" => Some(0.0),
+                s => s
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|r| (0.0..=MAX_RATE).contains(r)),
+            };
+            match (rate(&savings), rate(&credit), basis, compounding) {
+                (Some(savings_rate), Some(credit_rate), Some(basis), Some(compounding)) => {
+                    set_interest.emit(Some(Interest {
+                        savings_rate,
+                        credit_rate,
+                        basis,
+                        compounding,
+                    }))
+                }
+                _ => gloo_console::log!(format!("interest: invalid rate")),
+            }
+        }
+    };
+
+    let remove = {
+        let set_interest = props.set_interest.clone();
+        move |_| set_interest.emit(None)
+    };
+
+    html! {
+        <section class={classes!("w3-container", "w3-content")}>
+            <h3>{props.title.clone()}</h3>
+            <p>{match current {
+                Some(i) => format!(
+                    "Earning {}% and charged {}% when overdrawn, {}, compounding {}.",
+                    i.savings_rate,
+                    i.credit_rate,
+                    i.basis,
+                    i.compounding.to_string().to_lowercase()
+                ),
+                None => "No interest.".to_owned(),
+            }}</p>
+            <input onchange={set_from_input(savings_handle.clone())}
+                type="number"
+                step="0.01"
+                placeholder="Savings rate %"
+                value={(*savings_handle).clone()}
+            />
+            <input onchange={set_from_input(credit_handle.clone())}
+                type="number"
+                step="0.01"
+                placeholder="Overdraft rate %"
+                value={(*credit_handle).clone()}
+            />
+            <select onchange={set_from_select(basis_handle.clone())}>
+                {for RateBasis::ALL.iter().map(|b| html!{
+                    <option value={b.key()} selected={*basis_handle == b.key()}>
+                        {b.to_string()}
+                    </option>
+                })}
+            </select>
+            <select onchange={set_from_select(compounding_handle.clone())}>
+                {for Compounding::ALL.iter().map(|c| html!{
+                    <option value={c.key()} selected={*compounding_handle == c.key()}>
+                        {format!("Compounding {}", c.to_string().to_lowercase())}
+                    </option>
+                })}
+            </select>
+            <button onclick={submit}>{"Set"}</button>
+            if current.is_some() {
+                <button onclick={remove}>{"Remove"}</button>
+            }
+        </section>
+    }
+}
@@ -0,0 +1,46 @@
+use crate::storage;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct LoadErrorScreenProps {
+    pub error: String,
+    /// the stored log as saved, if it could be read at all
+    pub raw: Option<String>,
+    /// discard the stored log and start from the sample data
+    pub start_over: Callback<()>,
+}
+
+/// Shown in place of the planner when the stored log can't be loaded, so
+/// that it isn't saved over before the user has had a chance to keep it.
+#[function_component(LoadErrorScreen)]
+pub fn load_error_screen(props: &LoadErrorScreenProps) -> Html {
+    let export = props.raw.clone().map(|raw| {
+        move |_| {
+            if let Err(e) = storage::download(
+                "financial-planner-log-unreadable.yaml",
+                "application/yaml",
+                raw.as_bytes(),
+            ) {
+                gloo_console::log!(format!("export stored log: {e}"));
+            }
+        }
+    });
+
+    let start_over = {
+        let start_over = props.start_over.clone();
+        move |_| start_over.emit(())
+    };
+
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        <h3>{"The stored log could not be loaded"}</h3>
+        <p class={classes!("w3-text-red")}>{props.error.clone()}</p>
+        if let Some(export) = export {
+            <p>{"Export it to keep a copy before starting over."}</p>
+            <button onclick={export}>{"Export stored log"}</button>
+        }
+        <p>{"Starting over replaces the stored log with sample data."}</p>
+        <button onclick={start_over}>{"Start over"}</button>
+    </section>
+    }
+}
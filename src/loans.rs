@@ -0,0 +1,177 @@
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct LoanFormProps {
+    pub title: String,
+    /// adds the payments to the log
+    pub submit: Callback<Vec<Transaction>>,
+}
+
+fn set_from_input(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(input) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+        {
+            handle.set(input.value());
+        }
+    }
+}
+
+/// A form for a loan or mortgage, previewing its amortization schedule and
+/// payoff date before its payments are added to the log.
+#[function_component(LoanForm)]
+pub fn loan_form(props: &LoanFormProps) -> Html {
+    let name_handle = use_state(String::default);
+    let principal_handle = use_state(String::default);
+    let rate_handle = use_state(String::default);
+    let term_handle = use_state(String::default);
+    let first_payment_handle = use_state(String::default);
+    let extra_handle = use_state(String::default);
+    let lump_date_handle = use_state(String::default);
+    let lump_value_handle = use_state(String::default);
+
+    // blank optional fields count as nothing extra
+    let loan = (|| {
+        let lump_sums = match lump_value_handle.trim() {
+            "" => vec![],
+            value => vec![(lump_date_handle.parse().ok()?, value.parse().ok()?)],
+        };
+        Some(Loan {
+            name: name_handle.trim().to_owned(),
+            principal: principal_handle.trim().parse().ok()?,
+            annual_rate: rate_handle.trim().parse().ok()?,
+            term_months: term_handle.trim().parse().ok()?,
+            first_payment: first_payment_handle.parse().ok()?,
+            extra: match extra_handle.trim() {
+                "" => 0,
+                extra => extra.parse().ok()?,
+            },
+            lump_sums,
+        })
+    })();
+    let schedule = loan
+        .as_ref()
+        .map(|loan| -> Result<_, LoanError> { Ok((loan.regular_payment()?, loan.schedule()?)) });
+
+    let submit = {
+        let submit = props.submit.clone();
+        let loan = loan.clone();
+        let handles = [
+            name_handle.clone(),
+            principal_handle.clone(),
+            rate_handle.clone(),
+            term_handle.clone(),
+            first_payment_handle.clone(),
+            extra_handle.clone(),
+            lump_date_handle.clone(),
+            lump_value_handle.clone(),
+        ];
+        move |_| {
+            let Some(loan) = &loan else {
+                return gloo_console::log!(format!("loan: missing or invalid fields"));
+            };
+            match loan.transactions() {
+                Ok(transactions) => {
+                    submit.emit(transactions);
+                    for handle in &handles {
+                        handle.set(String::default());
+                    }
+                }
+                Err(e) => gloo_console::log!(format!("loan: {e}")),
+            }
+        }
+    };
+
+    html! {
+        <section class={classes!("w3-container", "w3-content")}>
+            <h3>{props.title.clone()}</h3>
+            <input onchange={set_from_input(name_handle.clone())}
+                type="text"
+                placeholder="Lender"
+                value={(*name_handle).clone()}
+            />
+            <input onchange={set_from_input(principal_handle.clone())}
+                type="number"
+                placeholder="Principal"
+                value={(*principal_handle).clone()}
+            />
+            <input onchange={set_from_input(rate_handle.clone())}
+                type="number"
+                step="0.01"
+                placeholder="Yearly rate %"
+                value={(*rate_handle).clone()}
+            />
+            <input onchange={set_from_input(term_handle.clone())}
+                type="number"
+                placeholder="Term in months"
+                value={(*term_handle).clone()}
+            />
+            <label>{" first payment "}</label>
+            <input onchange={set_from_input(first_payment_handle.clone())}
+                type="date"
+                value={(*first_payment_handle).clone()}
+            />
+            <p>
+                <input onchange={set_from_input(extra_handle.clone())}
+                    type="number"
+                    placeholder="Extra each month"
+                    value={(*extra_handle).clone()}
+                />
+                <input onchange={set_from_input(lump_value_handle.clone())}
+                    type="number"
+                    placeholder="One-off extra"
+                    value={(*lump_value_handle).clone()}
+                />
+                <label>{" on "}</label>
+                <input onchange={set_from_input(lump_date_handle.clone())}
+                    type="date"
+                    value={(*lump_date_handle).clone()}
+                />
+            </p>
+            <button onclick={submit}>{"Add payments"}</button>
+            {match (&loan, &schedule) {
+                (Some(loan), Some(Ok((payment, schedule)))) => {
+                    let interest: i64 = schedule.iter().map(|p| p.interest as i64).sum();
+                    html!{
+                    <>
+                    <p>{format!(
+                        "{} payments of {}{}, paid off on {}, with {interest} in interest.",
+                        schedule.len(),
+                        payment,
+                        if loan.extra > 0 { format!(" plus {}", loan.extra) } else { String::new() },
+                        schedule.last().map(|p| p.date.to_string()).unwrap_or_default(),
+                    )}</p>
+                    <details>
+                        <summary>{"Amortization schedule"}</summary>
+                        <table class={classes!("w3-table", "w3-striped")}>
+                            <tr>
+                                <th>{"#"}</th>
+                                <th>{"Date"}</th>
+                                <th>{"Principal"}</th>
+                                <th>{"Interest"}</th>
+                                <th>{"Balance"}</th>
+                            </tr>
+                            {for schedule.iter().map(|p| html!{
+                                <tr key={p.number}>
+                                    <td>{p.number}</td>
+                                    <td>{p.date}</td>
+                                    <td>{p.principal}</td>
+                                    <td>{p.interest}</td>
+                                    <td>{p.balance}</td>
+                                </tr>
+                            })}
+                        </table>
+                    </details>
+                    </>
+                    }
+                }
+                (_, Some(Err(e))) => html!{<p class={classes!("w3-text-red")}>{e.to_string()}</p>},
+                _ => html!{},
+            }}
+        </section>
+    }
+}
@@ -1,15 +1,22 @@
-#[cfg(test)]
-extern crate quickcheck;
-#[cfg(test)]
-#[macro_use(quickcheck)]
-extern crate quickcheck_macros;
-
 mod app;
-mod app_state;
+mod balance_warnings;
+mod charts;
 mod timeline;
 mod transactions_form;
 mod transactions_list;
 mod debug_window;
+mod inflation;
+mod interest;
+mod load_error_screen;
+mod loans;
+mod reconciliation;
+mod rules;
+mod scenarios;
+mod schedules;
+mod storage;
+mod storage_controls;
+mod unlock_screen;
+mod variance;
 
 use app::App;
 
@@ -18,8 +25,20 @@ fn main() {
 }
 
 mod components {
+    pub use super::balance_warnings::BalanceWarnings;
     pub use super::timeline::Timeline;
     pub use super::transactions_form::TransactionForm;
     pub use super::transactions_list::TransactionsList;
     pub use super::debug_window::DebugWindow;
+    pub use super::inflation::InflationForm;
+    pub use super::interest::InterestForm;
+    pub use super::load_error_screen::LoadErrorScreen;
+    pub use super::loans::LoanForm;
+    pub use super::reconciliation::ReconciliationForm;
+    pub use super::rules::{RuleForm, RulesList};
+    pub use super::scenarios::ScenarioPanel;
+    pub use super::schedules::{ScheduleForm, SchedulesList};
+    pub use super::storage_controls::StorageControls;
+    pub use super::unlock_screen::UnlockScreen;
+    pub use super::variance::VarianceReport;
 }
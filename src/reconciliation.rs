@@ -0,0 +1,145 @@
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ReconciliationFormProps {
+    pub title: String,
+    pub transaction_records: Vec<TransactionRecord>,
+    /// the statements reconciled so far, oldest first
+    pub reconciliations: Vec<Reconciliation>,
+    /// the statement being reconciled, once its date and balance are valid
+    pub statement: Option<Reconciliation>,
+    pub set_statement: Callback<Option<Reconciliation>>,
+    /// what the statement says less the cleared balance
+    pub difference: Option<i64>,
+    pub set_cleared: Callback<(TransactionId, bool)>,
+    pub reconcile: Callback<Reconciliation>,
+}
+
+/// Checks the log against a bank statement: transactions are ticked off
+/// until the cleared balance matches the statement's, and are then
+/// reconciled and locked.
+#[function_component(ReconciliationForm)]
+pub fn reconciliation_form(props: &ReconciliationFormProps) -> Html {
+    let date_handle = use_state(String::default);
+    let balance_handle = use_state(String::default);
+
+    // the statement is only passed up once both its fields are valid
+    let set_from_input = |is_date: bool| {
+        let date_handle = date_handle.clone();
+        let balance_handle = balance_handle.clone();
+        let set_statement = props.set_statement.clone();
+        move |e: Event| {
+            let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            else {
+                return;
+            };
+            let (date, balance) = if is_date {
+                date_handle.set(input.value());
+                (input.value(), (*balance_handle).clone())
+            } else {
+                balance_handle.set(input.value());
+                ((*date_handle).clone(), input.value())
+            };
+            set_statement.emit(
+                date.parse::<Date>()
+                    .ok()
+                    .zip(balance.parse::<Dollars>().ok())
+                    .map(|(statement_date, statement_balance)| Reconciliation {
+                        statement_date,
+                        statement_balance,
+                    }),
+            );
+        }
+    };
+    let on_date_change = set_from_input(true);
+    let on_balance_change = set_from_input(false);
+
+    let finish = {
+        let reconcile = props.reconcile.clone();
+        let set_statement = props.set_statement.clone();
+        let statement = props.statement.clone();
+        let date_handle = date_handle.clone();
+        let balance_handle = balance_handle.clone();
+        move |_| {
+            if let Some(statement) = statement.clone() {
+                reconcile.emit(statement);
+                set_statement.emit(None);
+                date_handle.set(String::default());
+                balance_handle.set(String::default());
+            }
+        }
+    };
+
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        <h3>{props.title.clone()}</h3>
+        {for props.reconciliations.last().map(|r| html!{
+            <p>{format!(
+                "Last reconciled to {} on {}.",
+                r.statement_balance, r.statement_date
+            )}</p>
+        })}
+        <label>{"Statement date "}</label>
+        <input onchange={on_date_change}
+            type="date"
+            value={(*date_handle).clone()}
+        />
+        <label>{" ending balance "}</label>
+        <input onchange={on_balance_change}
+            type="number"
+            value={(*balance_handle).clone()}
+        />
+        {for props.statement.iter().map(|statement| {
+            let difference = props.difference.unwrap_or_default();
+            html!{
+            <>
+            <ul id="reconciliation-list" class={classes!("w3-ul")}>
+                {for props.transaction_records.iter()
+                    .filter(|tr| {
+                        tr.transaction.date <= statement.statement_date
+                            && tr.status != TransactionStatus::Reconciled
+                            && !tr.transaction.planned
+                    })
+                    .map(|tr| {
+                        let set_cleared = props.set_cleared.clone();
+                        let id = tr.id;
+                        let onchange = move |e: Event| {
+                            if let Some(input) = e
+                                .target()
+                                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                            {
+                                set_cleared.emit((id, input.checked()));
+                            }
+                        };
+                        html!{
+                        <li key={id}>
+                            <input type="checkbox"
+                                checked={tr.status == TransactionStatus::Cleared}
+                                {onchange}
+                            />
+                            {format!(
+                                " {} {} {} {}",
+                                tr.transaction.date,
+                                tr.transaction.kind,
+                                tr.transaction.value,
+                                tr.transaction.payee
+                            )}
+                        </li>
+                        }
+                    })}
+            </ul>
+            <p>{format!("Difference: {difference}")}</p>
+            <button disabled={difference != 0} onclick={finish.clone()}>
+                {"Finish reconciliation"}
+            </button>
+            </>
+            }
+        })}
+    </section>
+    }
+}
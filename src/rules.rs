@@ -0,0 +1,147 @@
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct RuleFormProps {
+    pub title: String,
+    pub submit: Callback<Rule>,
+    /// the transactions the rule is previewed against
+    pub transaction_records: Vec<TransactionRecord>,
+}
+
+fn set_from_input(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(input) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+        {
+            handle.set(input.value());
+        }
+    }
+}
+
+fn set_from_select(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(select) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+        {
+            handle.set(select.value());
+        }
+    }
+}
+
+/// A form for a rule that categorizes transactions by payee as they are
+/// entered, with a preview of the existing transactions it would have
+/// changed.
+#[function_component(RuleForm)]
+pub fn rule_form(props: &RuleFormProps) -> Html {
+    let payee_handle = use_state(String::default);
+    let category_handle = use_state(String::default);
+    let kind_handle = use_state(String::default);
+
+    let rule = Rule {
+        payee_contains: payee_handle.trim().to_owned(),
+        category: category_handle.trim().to_owned(),
+        kind: match kind_handle.as_str() {
+            "income" => Some(TransactionKind::Income),
+            "expense" => Some(TransactionKind::Expense),
+            _ => None,
+        },
+    };
+    let preview = rule.preview(&props.transaction_records);
+
+    let submit = {
+        let submit = props.submit.clone();
+        let rule = rule.clone();
+        move |_| {
+            if rule.payee_contains.is_empty() {
+                return gloo_console::log!(format!("rule: no payee to match"));
+            }
+            if rule.category.is_empty() {
+                return gloo_console::log!(format!("rule: no category"));
+            }
+            submit.emit(rule.clone());
+        }
+    };
+
+    html! {
+        <section class={classes!("w3-container", "w3-content")}>
+            <h3>{props.title.clone()}</h3>
+            <label>{"Payee contains "}</label>
+            <input onchange={set_from_input(payee_handle.clone())}
+                type="text"
+                value={(*payee_handle).clone()}
+            />
+            <label>{" category "}</label>
+            <input onchange={set_from_input(category_handle.clone())}
+                type="text"
+                value={(*category_handle).clone()}
+            />
+            <select onchange={set_from_select(kind_handle.clone())}>
+                <option value="" selected={kind_handle.is_empty()}>{"Keep kind"}</option>
+                <option value="expense" selected={*kind_handle == "expense"}>{"Expense"}</option>
+                <option value="income" selected={*kind_handle == "income"}>{"Income"}</option>
+            </select>
+            <button onclick={submit}>{"Submit"}</button>
+            if !rule.payee_contains.is_empty() {
+                <p>{format!(
+                    "Would have changed {} existing transactions{}",
+                    preview.len(),
+                    if preview.is_empty() { "." } else { ":" }
+                )}</p>
+                <ul>
+                    {for preview.iter().map(|(tr, changed)| html!{
+                        <li key={tr.id}>
+                            {format!(
+                                "{} {} {}: {}",
+                                tr.transaction.date,
+                                tr.transaction.payee,
+                                tr.transaction.value,
+                                changed.category
+                            )}
+                            if changed.kind != tr.transaction.kind {
+                                {format!(", {} instead of {}", changed.kind, tr.transaction.kind)}
+                            }
+                        </li>
+                    })}
+                </ul>
+            }
+        </section>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RulesListProps {
+    pub data: Vec<RuleRecord>,
+    pub remove_rule: Callback<RuleId>,
+    pub title: String,
+}
+
+#[function_component(RulesList)]
+pub fn rules_list(props: &RulesListProps) -> Html {
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        <h3>{props.title.clone()}</h3>
+        <ol id="rules-list">
+            {for props.data.iter().map(|rr| {
+                let remove_rule = props.remove_rule.clone();
+                let id = rr.id;
+                let Rule { payee_contains, category, kind } = rr.rule.clone();
+                html!{
+                    <li key={id}>
+                        {format!("Payee contains \"{payee_contains}\": {category}")}
+                        {for kind.map(|kind| format!(", {kind}"))}
+                        {" "}
+                        <button onclick={move |_| remove_rule.emit(id)}>
+                            {"Delete"}
+                        </button>
+                    </li>
+                }
+            })}
+        </ol>
+    </section>
+    }
+}
@@ -0,0 +1,217 @@
+use crate::components::{LoanForm, ScheduleForm, TransactionForm};
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ScenarioPanelProps {
+    pub title: String,
+    pub names: Vec<String>,
+    /// the scenario shown on the timeline, if any
+    pub selected: Option<String>,
+    pub select: Callback<Option<String>>,
+    pub fork: Callback<String>,
+    pub drop_scenario: Callback<String>,
+    /// the log as it would be in the selected scenario
+    pub log: Option<Log>,
+    /// the selected scenario's hypothetical entries, oldest first
+    pub entries: Vec<Entry>,
+    /// adds an entry to the selected scenario only
+    pub branch: Callback<Entry>,
+}
+
+/// a line describing a scenario's hypothetical entry
+fn describe(entry: &Entry) -> String {
+    let transaction = |t: &Transaction| {
+        let mut s = format!("{} of {} on {}", t.kind, t.value, t.date);
+        if !t.payee.is_empty() {
+            s += &format!(" to {}", t.payee);
+        }
+        s
+    };
+    match entry {
+        Entry::Create(t) => transaction(t),
+        Entry::Delete(id) => format!("Leave out transaction {id}"),
+        Entry::Schedule(s) => format!("{}, {}", transaction(&s.transaction), s.frequency),
+        Entry::Unschedule(id) => format!("Stop recurring transaction {id}"),
+        entry => format!("{entry:?}"),
+    }
+}
+
+/// What-if scenarios branched off the log. Changes made here only apply
+/// to the selected scenario, whose balance the timeline draws over the
+/// real one.
+#[function_component(ScenarioPanel)]
+pub fn scenario_panel(props: &ScenarioPanelProps) -> Html {
+    let name_handle = use_state(String::default);
+    let leave_out_handle = use_state(String::default);
+
+    let on_name_change = {
+        let name_handle = name_handle.clone();
+        move |e: Event| {
+            if let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            {
+                name_handle.set(input.value());
+            }
+        }
+    };
+
+    let fork = {
+        let fork = props.fork.clone();
+        let name_handle = name_handle.clone();
+        let names = props.names.clone();
+        move |_| {
+            let name = name_handle.trim().to_owned();
+            if name.is_empty() {
+                return gloo_console::log!(format!("scenario: no name"));
+            }
+            if names.contains(&name) {
+                return gloo_console::log!(format!("scenario: {name} already exists"));
+            }
+            fork.emit(name);
+            name_handle.set(String::default());
+        }
+    };
+
+    let on_select = {
+        let select = props.select.clone();
+        move |e: Event| {
+            if let Some(s) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                select.emit(Some(s.value()).filter(|name| !name.is_empty()));
+            }
+        }
+    };
+
+    let on_leave_out_change = {
+        let leave_out_handle = leave_out_handle.clone();
+        move |e: Event| {
+            if let Some(s) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                leave_out_handle.set(s.value());
+            }
+        }
+    };
+
+    let leave_out = {
+        let branch = props.branch.clone();
+        let leave_out_handle = leave_out_handle.clone();
+        move |_| match leave_out_handle.parse::<TransactionId>() {
+            Ok(id) => {
+                branch.emit(Entry::Delete(id));
+                leave_out_handle.set(String::default());
+            }
+            Err(e) => gloo_console::log!(format!("leave out: {e}")),
+        }
+    };
+
+    let add_loan = {
+        let branch = props.branch.clone();
+        Callback::from(move |transactions: Vec<Transaction>| {
+            for t in transactions {
+                branch.emit(Entry::Create(t));
+            }
+        })
+    };
+
+    let scenario = props.selected.clone().zip(props.log.clone());
+
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        <h3>{props.title.clone()}</h3>
+        <label>{"New scenario "}</label>
+        <input onchange={on_name_change}
+            type="text"
+            value={(*name_handle).clone()}
+        />
+        <button onclick={fork}>{"Create"}</button>
+        <p>
+            <label>{"Compare with "}</label>
+            <select onchange={on_select}>
+                <option value="" selected={props.selected.is_none()}>{"No scenario"}</option>
+                {for props.names.iter().map(|name| html!{
+                    <option
+                        value={name.clone()}
+                        selected={props.selected.as_ref() == Some(name)}
+                    >{name.clone()}</option>
+                })}
+            </select>
+        </p>
+        {for scenario.map(|(name, log)| {
+            let drop_scenario = props.drop_scenario.clone();
+            let drop_name = name.clone();
+            let transaction_records = log.transaction_records();
+            let rule_records = log.rule_records();
+            html!{
+            <>
+            <button onclick={move |_| drop_scenario.emit(drop_name.clone())}>
+                {format!("Drop \"{name}\"")}
+            </button>
+            <h4>{"Changes in this scenario"}</h4>
+            if props.entries.is_empty() {
+                <p>{"None yet: add hypothetical transactions below."}</p>
+            }
+            <ol id="scenario-entries">
+                {for props.entries.iter().map(|entry| html!{<li>{describe(entry)}</li>})}
+            </ol>
+            <TransactionForm
+                title={"Hypothetical Income"}
+                kind={TransactionKind::Income}
+                submit={props.branch.reform(Entry::Create)}
+                transaction_records={transaction_records.clone()}
+                rule_records={rule_records.clone()}
+            />
+            <TransactionForm
+                title={"Hypothetical Expense"}
+                kind={TransactionKind::Expense}
+                submit={props.branch.reform(Entry::Create)}
+                transaction_records={transaction_records.clone()}
+                rule_records={rule_records}
+            />
+            <ScheduleForm
+                title={"Hypothetical Recurring Transaction"}
+                submit={props.branch.reform(Entry::Schedule)}
+            />
+            <LoanForm
+                title={"Hypothetical Loan"}
+                submit={add_loan}
+            />
+            <h4>{"Leave out"}</h4>
+            <select onchange={on_leave_out_change}>
+                <option value="" selected={leave_out_handle.is_empty()}>{"Choose a transaction"}</option>
+                {for transaction_records.iter().map(|tr| html!{
+                    <option
+                        value={tr.id.to_string()}
+                        selected={*leave_out_handle == tr.id.to_string()}
+                    >{format!("{}: {}", tr.id, describe(&Entry::Create(tr.transaction.clone())))}</option>
+                })}
+            </select>
+            <button onclick={leave_out}>{"Leave out"}</button>
+            <ul id="scenario-schedules" class={classes!("w3-ul")}>
+                {for log.schedule_records().iter().map(|sr| {
+                    let branch = props.branch.clone();
+                    let id = sr.id;
+                    html!{
+                    <li key={id}>
+                        {describe(&Entry::Schedule(sr.schedule.clone()))}
+                        {" "}
+                        <button onclick={move |_| branch.emit(Entry::Unschedule(id))}>
+                            {"Stop"}
+                        </button>
+                    </li>
+                    }
+                })}
+            </ul>
+            </>
+            }
+        })}
+    </section>
+    }
+}
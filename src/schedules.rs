@@ -0,0 +1,173 @@
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ScheduleFormProps {
+    pub title: String,
+    pub submit: Callback<Schedule>,
+}
+
+fn set_from_input(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(input) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+        {
+            handle.set(input.value());
+        }
+    }
+}
+
+fn set_from_select(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(select) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+        {
+            handle.set(select.value());
+        }
+    }
+}
+
+/// A form for a transaction that repeats, used for forecasting.
+#[function_component(ScheduleForm)]
+pub fn schedule_form(props: &ScheduleFormProps) -> Html {
+    let kind_handle = use_state(|| "expense".to_owned());
+    let value_handle = use_state(String::default);
+    let date_handle = use_state(String::default);
+    let frequency_handle = use_state(|| Frequency::Monthly.key().to_owned());
+    let until_handle = use_state(String::default);
+    let indexed_handle = use_state(|| false);
+
+    let submit = {
+        let submit = props.submit.clone();
+        let kind = (*kind_handle).clone();
+        let value = (*value_handle).clone();
+        let date = (*date_handle).clone();
+        let frequency = (*frequency_handle).clone();
+        let until = (*until_handle).clone();
+        let indexed = *indexed_handle;
+        move |_| {
+            let kind = match kind.as_str() {
+                "income" => TransactionKind::Income,
+                _ => TransactionKind::Expense,
+            };
+            let value = match value.parse::<u16>() {
+                Ok(value) => value as Dollars,
+                Err(e) => return gloo_console::log!(format!("value: {e:?}")),
+            };
+            let date = match date.parse::<Date>() {
+                Ok(date) => date,
+                Err(e) => return gloo_console::log!(format!("start date: {e:?}")),
+            };
+            let Ok(frequency) = frequency.parse::<Frequency>() else {
+                return gloo_console::log!(format!("could not parse frequency"));
+            };
+            let until = match until.as_str() {
+                "" => None,
+                until => match until.parse::<Date>() {
+                    Ok(until) if until >= date => Some(until),
+                    Ok(_) => return gloo_console::log!(format!("until: before start date")),
+                    Err(e) => return gloo_console::log!(format!("until: {e:?}")),
+                },
+            };
+            submit.emit(Schedule {
+                transaction: Transaction::new(value, kind, date),
+                frequency,
+                until,
+                indexed,
+            });
+        }
+    };
+
+    let on_indexed_change = {
+        let indexed_handle = indexed_handle.clone();
+        move |e: Event| {
+            if let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            {
+                indexed_handle.set(input.checked());
+            }
+        }
+    };
+
+    html! {
+        <section class={classes!("w3-container", "w3-content")}>
+            <h3>{props.title.clone()}</h3>
+            <select onchange={set_from_select(kind_handle.clone())}>
+                <option value="expense" selected={*kind_handle == "expense"}>{"Expense"}</option>
+                <option value="income" selected={*kind_handle == "income"}>{"Income"}</option>
+            </select>
+            <input onchange={set_from_input(value_handle.clone())}
+                type="text"
+                value={(*value_handle).clone()}
+            />
+            <input onchange={set_from_input(date_handle.clone())}
+                type="date"
+                value={(*date_handle).clone()}
+            />
+            <select onchange={set_from_select(frequency_handle.clone())}>
+                {for Frequency::ALL.iter().map(|frequency| html!{
+                    <option
+                        value={frequency.key()}
+                        selected={*frequency_handle == frequency.key()}
+                    >{frequency.to_string()}</option>
+                })}
+            </select>
+            <label>{" until "}</label>
+            <input onchange={set_from_input(until_handle.clone())}
+                type="date"
+                value={(*until_handle).clone()}
+            />
+            <label>
+                <input type="checkbox" checked={*indexed_handle} onchange={on_indexed_change} />
+                {" Grows with inflation "}
+            </label>
+            <button onclick={submit}>{"Submit"}</button>
+        </section>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SchedulesListProps {
+    pub data: Vec<ScheduleRecord>,
+    pub unschedule: Callback<ScheduleId>,
+    pub title: String,
+}
+
+#[function_component(SchedulesList)]
+pub fn schedules_list(props: &SchedulesListProps) -> Html {
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        <h3>{props.title.clone()}</h3>
+        <ol id="schedules-list">
+            {for props.data.iter().map(|sr| {
+                let unschedule = props.unschedule.clone();
+                let id = sr.id;
+                let Transaction {
+            value, kind, date, ..
+        } = sr.schedule.transaction.clone();
+                html!{
+                    <>
+                    <p>{"Kind: "}{kind}</p>
+                    <p>{"Value: "}{value}</p>
+                    <p>
+                        {sr.schedule.frequency.to_string()}{" from "}{date}
+                        {for sr.schedule.until.map(|until| html!{<>{" until "}{until}</>})}
+                        if sr.schedule.indexed {
+                            {", growing with inflation"}
+                        }
+                    </p>
+                    <button onclick={move |_| unschedule.emit(id)}>
+                        {"Delete"}
+                    </button>
+                    </>
+                }
+            })}
+        </ol>
+    </section>
+    }
+}
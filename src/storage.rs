@@ -0,0 +1,94 @@
+use financial_planner_core::stored_log::{StoredLog, StoredLogError};
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Storage, Url};
+
+const STORAGE_KEY: &str = "financial-planner/log";
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum StorageError {
+    Browser(String),
+    StoredLog(StoredLogError),
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use StorageError::*;
+        match self {
+            Browser(e) => write!(f, "browser: {e}"),
+            StoredLog(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<StoredLogError> for StorageError {
+    fn from(value: StoredLogError) -> Self {
+        Self::StoredLog(value)
+    }
+}
+
+fn local_storage() -> Result<Storage, StorageError> {
+    web_sys::window()
+        .ok_or_else(|| StorageError::Browser("global window does not exist".to_owned()))?
+        .local_storage()
+        .map_err(|e| StorageError::Browser(format!("{e:?}")))?
+        .ok_or_else(|| StorageError::Browser("localStorage is unavailable".to_owned()))
+}
+
+/// read the stored log as saved, without parsing it
+pub fn load_raw() -> Result<Option<String>, StorageError> {
+    local_storage()?
+        .get_item(STORAGE_KEY)
+        .map_err(|e| StorageError::Browser(format!("{e:?}")))
+}
+
+/// read the stored log, if one has been saved before
+pub fn load() -> Result<Option<StoredLog>, StorageError> {
+    match load_raw()? {
+        Some(s) => Ok(Some(StoredLog::from_yaml(&s)?)),
+        None => Ok(None),
+    }
+}
+
+pub fn save(stored_log: &StoredLog) -> Result<(), StorageError> {
+    local_storage()?
+        .set_item(STORAGE_KEY, &stored_log.to_yaml()?)
+        .map_err(|e| StorageError::Browser(format!("{e:?}")))
+}
+
+/// offer `stored_log` to the user as a downloaded YAML file
+pub fn export_file(filename: &str, stored_log: &StoredLog) -> Result<(), StorageError> {
+    download(
+        filename,
+        "application/yaml",
+        stored_log.to_yaml()?.as_bytes(),
+    )
+}
+
+/// offer `bytes` to the user as a downloaded file
+pub fn download(filename: &str, mime: &str, bytes: &[u8]) -> Result<(), StorageError> {
+    let browser = |e| StorageError::Browser(format!("{e:?}"));
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(browser)?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(browser)?;
+    download_url(filename, &url)?;
+    Url::revoke_object_url(&url).map_err(browser)
+}
+
+/// follow a link to `url` that saves it as `filename`
+pub fn download_url(filename: &str, url: &str) -> Result<(), StorageError> {
+    let anchor: HtmlAnchorElement = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| StorageError::Browser("expecting a document on window".to_owned()))?
+        .create_element("a")
+        .map_err(|e| StorageError::Browser(format!("{e:?}")))?
+        .dyn_into()
+        .map_err(|_| StorageError::Browser("anchor should be HtmlAnchorElement".to_owned()))?;
+    anchor.set_href(url);
+    anchor.set_download(filename);
+    anchor.click();
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct StorageControlsProps {
+    pub encrypted: bool,
+    pub set_passphrase: Callback<String>,
+    pub remove_passphrase: Callback<()>,
+    pub export: Callback<()>,
+}
+
+#[function_component(StorageControls)]
+pub fn storage_controls(props: &StorageControlsProps) -> Html {
+    let passphrase_handle = use_state(String::default);
+
+    let on_passphrase_change = {
+        let passphrase_handle = passphrase_handle.clone();
+        move |e: Event| {
+            if let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            {
+                passphrase_handle.set(input.value());
+            }
+        }
+    };
+
+    let set_passphrase = {
+        let passphrase_handle = passphrase_handle.clone();
+        let set_passphrase = props.set_passphrase.clone();
+        move |_| {
+            if !passphrase_handle.is_empty() {
+                set_passphrase.emit((*passphrase_handle).clone());
+                passphrase_handle.set(String::default());
+            }
+        }
+    };
+
+    let remove_passphrase = {
+        let remove_passphrase = props.remove_passphrase.clone();
+        move |_| remove_passphrase.emit(())
+    };
+
+    let export = {
+        let export = props.export.clone();
+        move |_| export.emit(())
+    };
+
+    html! {
+    <section
+        class={classes!("w3-container", "w3-card", "w3-padding-16")}
+    >
+        <p><b>{"Storage:"}</b></p>
+        <p>{if props.encrypted {
+            "Saved log is encrypted."
+        } else {
+            "Saved log is not encrypted."
+        }}</p>
+        <input onchange={on_passphrase_change}
+            type="password"
+            placeholder="New passphrase"
+            value={(*passphrase_handle).clone()}
+        />
+        <button onclick={set_passphrase}>{"Set passphrase"}</button>
+        {if props.encrypted {
+            html!{<button onclick={remove_passphrase}>{"Remove passphrase"}</button>}
+        } else {
+            html!{}
+        }}
+        <p><button onclick={export}>{"Export log"}</button></p>
+    </section>
+    }
+}
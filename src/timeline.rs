@@ -1,11 +1,14 @@
-use std::{error::Error, str::FromStr};
+use std::cell::RefCell;
+use std::str::FromStr;
 
-use crate::app_state::*;
-use chrono::{Duration, NaiveDate};
+use crate::charts::{ChartKind, Overlay, PeriodAt, EXPORT_SIZE};
+use crate::storage;
+use chrono::Weekday;
+use financial_planner_core::*;
 use plotters::prelude::*;
 use plotters_canvas::CanvasBackend;
 use wasm_bindgen::JsCast;
-use web_sys::{Element, HtmlCanvasElement, HtmlElement, HtmlInputElement};
+use web_sys::{Element, HtmlCanvasElement, HtmlElement, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -13,28 +16,137 @@ pub struct TimelineProps {
     pub data: Option<TimelineData>,
     pub title: String,
     pub canvas_id: String,
-    pub set_start_date: Callback<String>,
-    pub set_end_date: Callback<String>,
-    pub start_date: String,
-    pub end_date: String,
+    /// the only way the range is changed, whether by the date inputs, a
+    /// preset or zooming and panning the chart
+    pub set_date_range: Callback<DateRange>,
+    pub granularity: Granularity,
+    pub set_granularity: Callback<Granularity>,
+    pub forecast: bool,
+    pub set_forecast: Callback<bool>,
+    /// whether amounts are shown in today's dollars, or none if no
+    /// inflation is set to deflate them by
+    pub todays_dollars: Option<bool>,
+    pub set_todays_dollars: Callback<bool>,
+    pub threshold: Dollars,
+    /// a scenario's balance to compare against
+    pub overlay: Option<Overlay>,
+    pub select_period: Callback<DateRange>,
+    pub start_date: Date,
+    pub end_date: Date,
 }
 
-fn callback_from_input_element(cb: Callback<String>) -> Box<dyn Fn(Event) -> ()> {
-    Box::new(move |e: Event| {
+/// a handler for a date input, emitting the range `date_range` makes of
+/// the date entered
+fn on_date_change(
+    set_date_range: Callback<DateRange>,
+    name: &'static str,
+    date_range: impl Fn(Date) -> Result<DateRange, DateRangeError> + 'static,
+) -> impl Fn(Event) {
+    move |e: Event| {
         if let Some(input) = e
             .target()
             .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
         {
-            cb.emit(input.value())
+            match input.value().parse::<Date>() {
+                Ok(date) => match date_range(date) {
+                    Ok(date_range) => set_date_range.emit(date_range),
+                    Err(e) => gloo_console::log!(format!("{name}: {e}")),
+                },
+                Err(e) => gloo_console::log!(format!("{name}: {e:?}")),
+            }
         }
-    })
+    }
 }
 
 #[function_component(Timeline)]
 pub fn timeline(props: &TimelineProps) -> Html {
     let view_type_handle = use_state_eq(|| ViewType::Text);
-    let on_start_date_change = callback_from_input_element(props.set_start_date.clone());
-    let on_end_date_change = callback_from_input_element(props.set_end_date.clone());
+    let on_start_date_change = {
+        let end_date = props.end_date;
+        on_date_change(props.set_date_range.clone(), "start date", move |date| {
+            DateRange::new(date, end_date)
+        })
+    };
+    let on_end_date_change = {
+        let start_date = props.start_date;
+        on_date_change(props.set_date_range.clone(), "end date", move |date| {
+            DateRange::new(start_date, date)
+        })
+    };
+
+    let on_preset_change = {
+        let set_date_range = props.set_date_range.clone();
+        move |e: Event| {
+            if let Some(select) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                if let Ok(preset) = select.value().parse::<DateRangePreset>() {
+                    set_date_range.emit(preset.date_range(chrono::Local::now().date_naive()));
+                }
+                select.set_value("");
+            }
+        }
+    };
+
+    let week_start = match props.granularity {
+        Granularity::Week(week_start) => week_start,
+        _ => Weekday::Mon,
+    };
+
+    let on_granularity_change = {
+        let set_granularity = props.set_granularity.clone();
+        move |e: Event| {
+            if let Some(select) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                match Granularity::from_key(&select.value(), week_start) {
+                    Some(granularity) => set_granularity.emit(granularity),
+                    None => gloo_console::log!(format!("could not parse granularity")),
+                }
+            }
+        }
+    };
+
+    let on_week_start_change = {
+        let set_granularity = props.set_granularity.clone();
+        move |e: Event| {
+            if let Some(select) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                match select.value().parse::<Weekday>() {
+                    Ok(week_start) => set_granularity.emit(Granularity::Week(week_start)),
+                    Err(e) => gloo_console::log!(format!("week start: {e:?}")),
+                }
+            }
+        }
+    };
+
+    let on_forecast_change = {
+        let set_forecast = props.set_forecast.clone();
+        move |e: Event| {
+            if let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            {
+                set_forecast.emit(input.checked());
+            }
+        }
+    };
+
+    let on_todays_dollars_change = {
+        let set_todays_dollars = props.set_todays_dollars.clone();
+        move |e: Event| {
+            if let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            {
+                set_todays_dollars.emit(input.checked());
+            }
+        }
+    };
 
     let on_view_type_change = {
         let view_type_handle = view_type_handle.clone();
@@ -61,17 +173,26 @@ pub fn timeline(props: &TimelineProps) -> Html {
                 <p>{"Start Date: "}</p>
                 <input onchange={on_start_date_change}
                     type="date"
-                    value={props.start_date.clone()}
+                    value={props.start_date.to_string()}
                 />
             </div>
             <div class={classes!("w3-container", "w3-content", "w3-col", "l3", "m6", "s12")}>
                 <p>{"End Date: "}</p>
                 <input onchange={on_end_date_change}
                     type="date"
-                    value={props.end_date.clone()}
+                    value={props.end_date.to_string()}
                 />
             </div>
-            <div class={classes!("w3-container", "w3-content", "w3-col", "l3", "m12", "s12")}>
+            <div class={classes!("w3-container", "w3-content", "w3-col", "l3", "m6", "s12")}>
+                <p>{"Preset: "}</p>
+                <select onchange={on_preset_change}>
+                    <option value="" selected={true}>{"Choose a range"}</option>
+                    {for DateRangePreset::ALL.iter().map(|preset| html!{
+                        <option value={preset.key()}>{preset.to_string()}</option>
+                    })}
+                </select>
+            </div>
+            <div class={classes!("w3-container", "w3-content", "w3-col", "l3", "m6", "s12")}>
                 <p>{"View Type: "}</p>
                 <div class={classes!("w3-container", "w3-cell")}>
                     <input
@@ -97,20 +218,104 @@ pub fn timeline(props: &TimelineProps) -> Html {
                         for="histogram"
                     >{"Histogram"}</label>
                 </div>
+                <div class={classes!("w3-container", "w3-cell")}>
+                    <input
+                        type="radio"
+                        id="balance"
+                        name="view_type"
+                        value="balance"
+                        onchange={on_view_type_change.clone()}
+                    />
+                    <label
+                        for="balance"
+                    >{"Balance"}</label>
+                </div>
+                <div class={classes!("w3-container", "w3-cell")}>
+                    <input
+                        type="checkbox"
+                        id="forecast"
+                        checked={props.forecast}
+                        onchange={on_forecast_change}
+                    />
+                    <label
+                        for="forecast"
+                    >{"Forecast"}</label>
+                </div>
+                if let Some(todays_dollars) = props.todays_dollars {
+                    <div class={classes!("w3-container", "w3-cell")}>
+                        <input
+                            type="checkbox"
+                            id="todays-dollars"
+                            checked={todays_dollars}
+                            onchange={on_todays_dollars_change}
+                        />
+                        <label
+                            for="todays-dollars"
+                        >{"Today's dollars"}</label>
+                    </div>
+                }
+            </div>
+            <div class={classes!("w3-container", "w3-content", "w3-col", "l3", "m6", "s12")}>
+                <p>{"Group By: "}</p>
+                <select onchange={on_granularity_change}>
+                    {for [
+                        Granularity::Day,
+                        Granularity::Week(week_start),
+                        Granularity::Month,
+                        Granularity::Quarter,
+                        Granularity::Year,
+                    ].iter().map(|granularity| html!{
+                        <option
+                            value={granularity.key()}
+                            selected={granularity.key() == props.granularity.key()}
+                        >{granularity.to_string()}</option>
+                    })}
+                </select>
+                {if matches!(props.granularity, Granularity::Week(_)) {
+                    html!{
+                    <select onchange={on_week_start_change}>
+                        {for [Weekday::Mon, Weekday::Sun].iter().map(|day| html!{
+                            <option
+                                value={day.to_string()}
+                                selected={*day == week_start}
+                            >{format!("Weeks start on {day}")}</option>
+                        })}
+                    </select>
+                    }
+                } else {
+                    html!{}
+                }}
             </div>
         </div>
         <div class={classes!("w3-padding-32")}>
         {if let Some(data) = props.data.clone() {
             match view_type {
                 ViewType::Histogram => html!{
-                    <HistogramView
+                    <ChartView
+                        kind={ChartKind::Histogram}
+                        canvas_id={"my_canvas"}
+                        data={props.data.clone().unwrap_or_default()}
+                        threshold={props.threshold}
+                        overlay={props.overlay.clone()}
+                        select_period={props.select_period.clone()}
+                        set_date_range={props.set_date_range.clone()}
+                    />
+                },
+                ViewType::Balance => html!{
+                    <ChartView
+                        kind={ChartKind::Balance}
                         canvas_id={"my_canvas"}
                         data={props.data.clone().unwrap_or_default()}
+                        threshold={props.threshold}
+                        overlay={props.overlay.clone()}
+                        select_period={props.select_period.clone()}
+                        set_date_range={props.set_date_range.clone()}
                     />
                 },
                 ViewType::Text => html!{
                     <DateSummaryView
                         data={data}
+                        overlay={props.overlay.clone()}
                     />
                 },
             }
@@ -126,6 +331,7 @@ pub fn timeline(props: &TimelineProps) -> Html {
 enum ViewType {
     Text,
     Histogram,
+    Balance,
 }
 
 impl FromStr for ViewType {
@@ -134,20 +340,70 @@ impl FromStr for ViewType {
         match s {
             "text" => Ok(Self::Text),
             "histogram" => Ok(Self::Histogram),
+            "balance" => Ok(Self::Balance),
             _ => Err(()),
         }
     }
 }
 
 #[derive(Properties, PartialEq)]
-pub struct HistogramViewProps {
+pub struct ChartViewProps {
+    pub kind: ChartKind,
     pub canvas_id: String,
     pub data: TimelineData,
+    pub threshold: Dollars,
+    pub overlay: Option<Overlay>,
+    pub select_period: Callback<DateRange>,
+    pub set_date_range: Callback<DateRange>,
+}
+
+/// how much one step of the mouse wheel zooms in
+const ZOOM_STEP: f64 = 0.8;
+
+/// A mouse drag across the chart that pans it or, holding shift, selects
+/// the periods to zoom to.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Drag {
+    from: usize,
+    /// where the drag started, in CSS pixels
+    x: i32,
+    brush: bool,
+}
+
+/// The point under the mouse in the canvas's own pixels, which differ from
+/// the event's CSS pixels when the canvas is scaled.
+fn canvas_point(e: &MouseEvent) -> Option<(i32, i32)> {
+    let canvas = e.target()?.dyn_into::<HtmlCanvasElement>().ok()?;
+    let scale = |offset: i32, pixels: u32, client: i32| {
+        if client > 0 {
+            (offset as f64 * pixels as f64 / client as f64) as i32
+        } else {
+            offset
+        }
+    };
+    Some((
+        scale(e.offset_x(), canvas.width(), canvas.client_width()),
+        scale(e.offset_y(), canvas.height(), canvas.client_height()),
+    ))
 }
 
-#[function_component(HistogramView)]
-pub fn histogram(props: &HistogramViewProps) -> Html {
+fn period_under(period_at: &RefCell<Option<PeriodAt>>, e: &MouseEvent) -> Option<usize> {
+    let point = canvas_point(e)?;
+    period_at.borrow().as_ref().and_then(|f| f(point))
+}
+
+#[function_component(ChartView)]
+pub fn chart_view(props: &ChartViewProps) -> Html {
     let style = use_state_eq(String::new);
+    let period_at = use_mut_ref(|| None::<PeriodAt>);
+    // the hovered period and where to show its tooltip, in CSS pixels
+    let hovered = use_state_eq(|| None::<(usize, i32, i32)>);
+    let drag = use_state_eq(|| None::<Drag>);
+    let periods: Vec<DateRange> = props.data.iter().map(|s| s.period.clone()).collect();
+    let span = periods
+        .first()
+        .zip(periods.last())
+        .and_then(|(first, last)| DateRange::new(first.start(), last.end()).ok());
     use_effect_with_deps(
         {
             let style = style.clone();
@@ -158,24 +414,236 @@ pub fn histogram(props: &HistogramViewProps) -> Html {
         },
         props.canvas_id.clone(),
     );
-    use_effect({
-        let data = props.data.clone();
-        let canvas_id = props.canvas_id.clone();
-        move || {
-            match draw_timeline(&canvas_id, data) {
-                Err(e) => gloo_console::log!(format!("{e:?}")),
+    // redrawn only when what is drawn changes, not as the mouse moves
+    use_effect_with_deps(
+        {
+            let period_at = period_at.clone();
+            move |(data, threshold, kind, overlay, canvas_id): &(
+                TimelineData,
+                Dollars,
+                ChartKind,
+                Option<Overlay>,
+                String,
+            )| {
+                let backend = CanvasBackend::new(canvas_id).expect("cannot find canvas");
+                match kind.draw(
+                    &backend.into_drawing_area(),
+                    data,
+                    *threshold,
+                    overlay.as_ref(),
+                ) {
+                    Err(e) => gloo_console::log!(format!("{e:?}")),
+                    Ok(f) => *period_at.borrow_mut() = Some(f),
+                }
+                || {}
+            }
+        },
+        (
+            props.data.clone(),
+            props.threshold,
+            props.kind,
+            props.overlay.clone(),
+            props.canvas_id.clone(),
+        ),
+    );
+    let on_mouse_move = {
+        let period_at = period_at.clone();
+        let hovered = hovered.clone();
+        move |e: MouseEvent| {
+            let n = period_under(&period_at, &e);
+            hovered.set(n.map(|n| (n, e.offset_x(), e.offset_y())));
+        }
+    };
+    let on_mouse_leave = {
+        let hovered = hovered.clone();
+        let drag = drag.clone();
+        move |_| {
+            hovered.set(None);
+            drag.set(None);
+        }
+    };
+    let on_mouse_down = {
+        let period_at = period_at.clone();
+        let drag = drag.clone();
+        move |e: MouseEvent| {
+            // don't select the page's text while dragging
+            e.prevent_default();
+            drag.set(period_under(&period_at, &e).map(|from| Drag {
+                from,
+                x: e.offset_x(),
+                brush: e.shift_key(),
+            }));
+        }
+    };
+    // releasing the mouse where it was pressed is a click on that period
+    let on_mouse_up = {
+        let period_at = period_at.clone();
+        let drag = drag.clone();
+        let periods = periods.clone();
+        let span = span.clone();
+        let select_period = props.select_period.clone();
+        let set_date_range = props.set_date_range.clone();
+        move |e: MouseEvent| {
+            let Some(Drag { from, brush, .. }) = *drag else {
+                return;
+            };
+            drag.set(None);
+            let Some(to) = period_under(&period_at, &e) else {
+                return;
+            };
+            let (Some(from_period), Some(to_period)) = (periods.get(from), periods.get(to)) else {
+                return;
+            };
+            if from == to {
+                select_period.emit(from_period.clone());
+            } else if brush {
+                let (first, last) = if from < to {
+                    (from_period, to_period)
+                } else {
+                    (to_period, from_period)
+                };
+                if let Ok(date_range) = DateRange::new(first.start(), last.end()) {
+                    set_date_range.emit(date_range);
+                }
+            } else {
+                // dragging to the right brings earlier dates into view
+                let days = (from_period.start() - to_period.start()).num_days();
+                if let Some(date_range) = span.as_ref().and_then(|span| span.shift(days)) {
+                    set_date_range.emit(date_range);
+                }
+            }
+        }
+    };
+    let on_wheel = {
+        let period_at = period_at.clone();
+        let set_date_range = props.set_date_range.clone();
+        move |e: WheelEvent| {
+            let Some(span) = &span else {
+                return;
+            };
+            e.prevent_default();
+            let anchor = period_under(&period_at, &e)
+                .and_then(|n| periods.get(n))
+                .map(|period| period.start())
+                .unwrap_or_else(|| span.start() + chrono::Duration::days(span.num_days() / 2));
+            let factor = if e.delta_y() < 0.0 {
+                ZOOM_STEP
+            } else {
+                1.0 / ZOOM_STEP
+            };
+            match span.zoom(anchor, factor) {
+                Some(zoomed) if zoomed != *span => set_date_range.emit(zoomed),
                 _ => {}
             }
-            || {}
         }
+    };
+    let brush = match (*drag, *hovered) {
+        (Some(Drag { x, brush: true, .. }), Some((_, to, _))) => html! {
+            <div
+                class={classes!("w3-pale-blue")}
+                style={format!(
+                    "position: absolute; left: {}px; width: {}px; top: 0; height: 100%; opacity: 0.5; pointer-events: none",
+                    x.min(to),
+                    x.abs_diff(to)
+                )}
+            />
+        },
+        _ => html! {},
+    };
+    let cursor = match *drag {
+        Some(Drag { brush: false, .. }) => "; cursor: grabbing",
+        Some(Drag { brush: true, .. }) => "; cursor: col-resize",
+        None => "",
+    };
+    let tooltip = (*hovered).and_then(|(n, x, y)| {
+        let summary = props.data.iter().nth(n)?;
+        Some(html! {
+        <div
+            class={classes!("w3-card", "w3-white", "w3-small", "w3-padding-small")}
+            style={format!("position: absolute; left: {}px; top: {}px; pointer-events: none", x + 12, y + 12)}
+        >
+            if summary.period.num_days() == 1 {
+                <b>{summary.period.start()}</b>
+            } else {
+                <b>{summary.period.start()}{" to "}{summary.period.end()}</b>
+            }
+            if summary.forecast {
+                <i>{" (forecast)"}</i>
+            }
+            <br />{"Income: "}{summary.income}
+            <br />{"Expenses: "}{summary.expenses}
+            <br />{"Balance: "}{summary.balance}
+            if summary.interest != 0 {
+                <br />{"Interest: "}{summary.interest}
+            }
+            {for props.overlay.iter().filter_map(|o| {
+                let balance = o.data.iter().nth(n)?.balance;
+                Some(html!{<><br />{format!("{} balance: {balance}", o.label)}</>})
+            })}
+        </div>
+        })
     });
+    let save_svg = {
+        let data = props.data.clone();
+        let overlay = props.overlay.clone();
+        let (kind, threshold) = (props.kind, props.threshold);
+        move |_| {
+            if let Err(e) = kind
+                .svg(&data, threshold, overlay.as_ref(), EXPORT_SIZE)
+                .and_then(|svg| {
+                    storage::download(
+                        &format!("{}.svg", kind.key()),
+                        "image/svg+xml",
+                        svg.as_bytes(),
+                    )
+                    .map_err(Into::into)
+                })
+            {
+                gloo_console::log!(format!("save svg: {e}"));
+            }
+        }
+    };
+
+    let save_png = {
+        let data = props.data.clone();
+        let overlay = props.overlay.clone();
+        let (kind, threshold) = (props.kind, props.threshold);
+        move |_| {
+            if let Err(e) = kind
+                .png_data_url(&data, threshold, overlay.as_ref(), EXPORT_SIZE)
+                .and_then(|url| {
+                    storage::download_url(&format!("{}.png", kind.key()), &url).map_err(Into::into)
+                })
+            {
+                gloo_console::log!(format!("save png: {e}"));
+            }
+        }
+    };
+
     html! {
     <>
-    <p>{"Note: No support for negative balances on histogram at this time."}</p>
-    <canvas
-        id={props.canvas_id.clone()}
-        style={(*style).clone()}
-    />
+    <button onclick={save_svg}>{"Save as SVG"}</button>
+    <button onclick={save_png}>{"Save as PNG"}</button>
+    {if props.kind == ChartKind::Histogram {
+        html!{<p>{"Note: the histogram shows negative balances as zero; use the balance view to see them."}</p>}
+    } else {
+        html!{}
+    }}
+    <p>{"Forecast periods are drawn as outlines. Click a period to list its transactions."}</p>
+    <p>{"Scroll to zoom, drag to pan, or hold shift and drag to zoom to a range."}</p>
+    <div style="position: relative">
+        <canvas
+            id={props.canvas_id.clone()}
+            style={format!("{}{cursor}", *style)}
+            onmousemove={on_mouse_move}
+            onmouseleave={on_mouse_leave}
+            onmousedown={on_mouse_down}
+            onmouseup={on_mouse_up}
+            onwheel={on_wheel}
+        />
+        {brush}
+        {tooltip.unwrap_or_default()}
+    </div>
     </>
     }
 }
@@ -183,6 +651,7 @@ pub fn histogram(props: &HistogramViewProps) -> Html {
 #[derive(Properties, PartialEq)]
 pub struct DateSummaryViewProps {
     data: TimelineData,
+    overlay: Option<Overlay>,
 }
 
 #[function_component(DateSummaryView)]
@@ -191,12 +660,26 @@ pub fn histogram(props: &DateSummaryViewProps) -> Html {
     <>
     <p><b>{"Date Summaries"}</b></p>
     <hr />
-    {for props.data.iter().map(|summary: &DateSummary| html!{
+    {for props.data.iter().enumerate().map(|(n, summary)| html!{
         <>
-        <p>{"Date: "}{summary.date}</p>
+        if summary.period.num_days() == 1 {
+            <p>{"Date: "}{summary.period.start()}</p>
+        } else {
+            <p>{"Period: "}{summary.period.start()}{" to "}{summary.period.end()}</p>
+        }
+        if summary.forecast {
+            <p><i>{"Forecast"}</i></p>
+        }
         <p>{"Income: "}{summary.income}</p>
         <p>{"Expenses: "}{summary.expenses}</p>
         <p>{"Balance: "}{summary.balance}</p>
+        if summary.interest != 0 {
+            <p>{"Interest: "}{summary.interest}</p>
+        }
+        {for props.overlay.iter().filter_map(|o| {
+            let balance = o.data.iter().nth(n)?.balance;
+            Some(html!{<p>{format!("{} balance: {balance}", o.label)}</p>})
+        })}
         <hr />
         </>
     })}
@@ -204,83 +687,6 @@ pub fn histogram(props: &DateSummaryViewProps) -> Html {
     }
 }
 
-fn draw_timeline(canvas_id: &str, data: TimelineData) -> Result<(), Box<dyn Error>> {
-    let start_date = match data.start_date() {
-        Some(d) => d,
-        _ => return Ok(()),
-    };
-    let end_date = start_date + Duration::days(data.len() as i64);
-    let backend = CanvasBackend::new(canvas_id).expect("cannot find canvas");
-    let root = backend.into_drawing_area();
-
-    root.fill(&WHITE)?;
-
-    let max = if let Some(value) = data
-        .iter()
-        .map(|v| v.income.max(v.expenses).max(v.balance))
-        .max()
-    {
-        math::round::floor(value as f64 * 1.1, 1).max(100.0) as u32
-    } else {
-        return Ok(());
-    };
-
-    let mut chart = ChartBuilder::on(&root)
-        .x_label_area_size(35)
-        .y_label_area_size(50)
-        .margin(5)
-        .build_cartesian_2d((start_date..end_date).into_segmented(), 0u32..max)?;
-
-    chart
-        .configure_mesh()
-        .disable_x_mesh()
-        .disable_y_mesh()
-        .x_label_formatter(&|v: &SegmentValue<NaiveDate>| match v {
-            SegmentValue::Exact(d) | SegmentValue::CenterOf(d) => d.format("%d").to_string(),
-            _ => "<em>SeinfeldHEUHH.mp3</em>".to_owned(),
-        })
-        .y_labels(10)
-        .bold_line_style(&WHITE.mix(0.3))
-        .y_desc("Dollars")
-        .x_desc("Date")
-        .axis_desc_style(("sans-serif", 15))
-        .draw()?;
-
-    chart.draw_series(
-        Histogram::vertical(&chart)
-            .style(BLUE.mix(0.5).filled())
-            .data(
-                data.iter()
-                    .enumerate()
-                    .map(|(n, s)| (start_date + Duration::days(n as i64), s.income as u32)),
-            ),
-    )?;
-
-    chart.draw_series(
-        Histogram::vertical(&chart)
-            .style(RED.mix(0.5).filled())
-            .data(
-                data.iter()
-                    .enumerate()
-                    .map(|(n, s)| (start_date + Duration::days(n as i64), s.expenses as u32)),
-            ),
-    )?;
-
-    chart.draw_series(
-        Histogram::vertical(&chart)
-            .style(BLACK.mix(0.5).filled())
-            .data(data.iter().enumerate().map(|(n, s)| {
-                (
-                    start_date + Duration::days(n as i64),
-                    s.balance.max(0) as u32,
-                )
-            })),
-    )?;
-
-    root.present()?;
-    Ok(())
-}
-
 fn compute_canvas_style(canvas_id: &str) -> String {
     // based on https://github.com/plotters-rs/plotters-wasm-demo/blob/38523cdba80ab5c0e65db62edee275901c27ce90/www/index.js#L45
     let window = web_sys::window().expect("global window does not exist");
@@ -1,27 +1,87 @@
-use crate::app_state::*;
+use financial_planner_core::*;
 use wasm_bindgen::JsCast;
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct TransactionFormProps {
     pub title: String,
-    pub submit: Callback<(Date, Dollars)>,
+    pub kind: TransactionKind,
+    pub submit: Callback<Transaction>,
+    /// past transactions, to autocomplete payees and suggest categories from
+    pub transaction_records: Vec<TransactionRecord>,
+    pub rule_records: Vec<RuleRecord>,
+}
+
+fn set_from_input(handle: UseStateHandle<String>) -> impl Fn(Event) {
+    move |e: Event| {
+        if let Some(input) = e
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+        {
+            handle.set(input.value());
+        }
+    }
 }
 
 #[function_component(TransactionForm)]
 pub fn transactions_form(props: &TransactionFormProps) -> Html {
     let value_handle = use_state(String::default);
     let date_handle = use_state(String::default);
+    let payee_handle = use_state(String::default);
+    let memo_handle = use_state(String::default);
+    let reference_handle = use_state(String::default);
+    let category_handle = use_state(String::default);
+    let planned_handle = use_state(|| false);
+    let fulfils_handle = use_state(String::default);
     let value = (*value_handle).clone();
     let date = (*date_handle).clone();
+    let suggestion = Suggestion::for_payee(
+        &payee_handle,
+        &props.rule_records,
+        &props.transaction_records,
+    );
+
+    // a suggested category fills in an empty category field, but never
+    // replaces one that was typed
+    let on_payee_change = {
+        let payee_handle = payee_handle.clone();
+        let category_handle = category_handle.clone();
+        let rule_records = props.rule_records.clone();
+        let transaction_records = props.transaction_records.clone();
+        move |e: Event| {
+            let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            else {
+                return;
+            };
+            let payee = input.value();
+            if category_handle.trim().is_empty() {
+                if let Some(suggestion) =
+                    Suggestion::for_payee(&payee, &rule_records, &transaction_records)
+                {
+                    category_handle.set(suggestion.category);
+                }
+            }
+            payee_handle.set(payee);
+        }
+    };
 
     let submit = {
-        let value_handle = value_handle.clone();
-        let date_handle = date_handle.clone();
         let submit = props.submit.clone();
+        let kind = props.kind;
         let value = (*value_handle).clone();
         let date = (*date_handle).clone();
+        let payee = payee_handle.trim().to_owned();
+        let memo = memo_handle.trim().to_owned();
+        let reference = Some(reference_handle.trim().to_owned()).filter(|r| !r.is_empty());
+        let category = category_handle.trim().to_owned();
+        let planned = *planned_handle;
+        let fulfils = fulfils_handle
+            .parse::<TransactionId>()
+            .ok()
+            .filter(|_| !planned);
         move |_| {
             let date = match date.parse::<Date>() {
                 Ok(date) => Some(date),
@@ -32,8 +92,16 @@ pub fn transactions_form(props: &TransactionFormProps) -> Html {
             };
             match value.parse::<u16>() {
                 Ok(value) => {
-                    if date.is_some() {
-                        submit.emit((date.unwrap(), value as Dollars));
+                    if let Some(date) = date {
+                        submit.emit(Transaction {
+                            payee: payee.clone(),
+                            memo: memo.clone(),
+                            reference: reference.clone(),
+                            category: category.clone(),
+                            planned,
+                            fulfils,
+                            ..Transaction::new(value as Dollars, kind, date)
+                        });
                     }
                 }
                 Err(e) => {
@@ -43,42 +111,105 @@ pub fn transactions_form(props: &TransactionFormProps) -> Html {
         }
     };
 
-    let on_value_change = {
-        let value_handle = value_handle.clone();
+    let on_planned_change = {
+        let planned_handle = planned_handle.clone();
         move |e: Event| {
             if let Some(input) = e
                 .target()
                 .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
             {
-                value_handle.set(input.value());
+                planned_handle.set(input.checked());
             }
         }
     };
-
-    let on_date_change = {
-        let date_handle = date_handle.clone();
+    let on_fulfils_change = {
+        let fulfils_handle = fulfils_handle.clone();
         move |e: Event| {
-            if let Some(input) = e
+            if let Some(select) = e
                 .target()
-                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
             {
-                date_handle.set(input.value());
+                fulfils_handle.set(select.value());
             }
         }
     };
+    let open_plans = props.transaction_records.iter().filter(|tr| {
+        tr.transaction.planned && tr.fulfilled_by.is_none() && tr.transaction.kind == props.kind
+    });
 
+    let kind_key = props.kind.to_string().to_lowercase();
+    let payees_id = format!("{kind_key}-payees");
+    let categories_id = format!("{kind_key}-categories");
     html! {
         <section class={classes!("w3-container", "w3-content")}>
             <h3>{props.title.clone()}</h3>
-            <input onchange={on_value_change}
+            <input onchange={set_from_input(value_handle.clone())}
                 type="text"
                 value={value}
             />
-            <input onchange={on_date_change}
+            <input onchange={set_from_input(date_handle.clone())}
                 type="date"
                 value={date}
             />
+            <input onchange={on_payee_change}
+                type="text"
+                placeholder="Payee"
+                list={payees_id.clone()}
+                value={(*payee_handle).clone()}
+            />
+            <datalist id={payees_id}>
+                {for payees(&props.transaction_records).into_iter().map(|payee| html!{
+                    <option value={payee} />
+                })}
+            </datalist>
+            <input onchange={set_from_input(category_handle.clone())}
+                type="text"
+                placeholder="Category"
+                list={categories_id.clone()}
+                value={(*category_handle).clone()}
+            />
+            <datalist id={categories_id}>
+                {for categories(&props.transaction_records, &props.rule_records)
+                    .into_iter()
+                    .map(|category| html!{<option value={category} />})}
+            </datalist>
+            <input onchange={set_from_input(reference_handle.clone())}
+                type="text"
+                placeholder="Reference"
+                value={(*reference_handle).clone()}
+            />
+            <input onchange={set_from_input(memo_handle.clone())}
+                type="text"
+                placeholder="Memo"
+                value={(*memo_handle).clone()}
+            />
+            <label>
+                <input type="checkbox" checked={*planned_handle} onchange={on_planned_change} />
+                {" Planned "}
+            </label>
+            if !*planned_handle {
+                <select onchange={on_fulfils_change}>
+                    <option value="" selected={fulfils_handle.is_empty()}>{"Unplanned"}</option>
+                    {for open_plans.map(|tr| {
+                        let id = tr.id.to_string();
+                        html!{
+                        <option value={id.clone()} selected={*fulfils_handle == id}>
+                            {format!(
+                                "Fulfils #{id}: {} {} {}",
+                                tr.transaction.date, tr.transaction.value, tr.transaction.payee
+                            )}
+                        </option>
+                        }
+                    })}
+                </select>
+            }
             <button onclick={submit}>{"Submit"}</button>
+            {for suggestion
+                .and_then(|s| s.kind)
+                .filter(|kind| *kind != props.kind)
+                .map(|kind| html!{
+                    <p>{format!("{} is usually entered as {kind}.", payee_handle.trim())}</p>
+                })}
         </section>
     }
 }
@@ -1,24 +1,249 @@
-use crate::app_state::*;
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
+/// how many transactions to render at a time
+const PAGE_SIZE: usize = 50;
+
 #[derive(Properties, PartialEq)]
 pub struct TransactionsListProps {
     pub data: Vec<TransactionRecord>,
     pub delete_transaction: Callback<TransactionId>,
     pub title: String,
+    /// only list transactions dated in this period
+    pub period: Option<DateRange>,
+    pub clear_period: Callback<()>,
+}
+
+/// a handler that updates the query from the value of the input or select
+/// it is attached to
+fn update_query(
+    query: &UseStateHandle<TransactionQuery>,
+    update: impl Fn(&mut TransactionQuery, String) + 'static,
+) -> Callback<Event> {
+    let query = query.clone();
+    Callback::from(move |e: Event| {
+        let Some(target) = e.target() else { return };
+        let value = match target.dyn_into::<HtmlInputElement>() {
+            Ok(input) => input.value(),
+            Err(target) => match target.dyn_into::<HtmlSelectElement>() {
+                Ok(select) => select.value(),
+                Err(_) => return,
+            },
+        };
+        let mut updated = (*query).clone();
+        update(&mut updated, value);
+        query.set(updated);
+    })
+}
+
+/// the value of an optional field, which is left out when empty or
+/// invalid
+fn optional<T: std::str::FromStr>(value: &str) -> Option<T> {
+    value.parse().ok()
+}
+
+fn input_value<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
 }
 
 #[function_component(TransactionsList)]
 pub fn transactions_list(props: &TransactionsListProps) -> Html {
+    let query_handle = use_state(TransactionQuery::default);
+    let query = (*query_handle).clone();
+    let records: Vec<&TransactionRecord> = query
+        .apply(&props.data)
+        .into_iter()
+        .filter(|tr| {
+            props
+                .period
+                .as_ref()
+                .is_none_or(|period| period.contains(&tr.transaction.date))
+        })
+        .collect();
+    let totals = Totals::of(records.iter().copied());
+
+    // back to the first page whenever the list is filtered differently
+    let page_handle = use_state_eq(|| 0);
+    use_effect_with_deps(
+        {
+            let page_handle = page_handle.clone();
+            move |_| page_handle.set(0)
+        },
+        (query.clone(), props.period.clone()),
+    );
+    let pages = records.len().div_ceil(PAGE_SIZE).max(1);
+    let page = (*page_handle).min(pages - 1);
+    let go_to = |page: usize| {
+        let page_handle = page_handle.clone();
+        move |_| page_handle.set(page)
+    };
+    let clear_filters = {
+        let query_handle = query_handle.clone();
+        move |_| {
+            query_handle.set(TransactionQuery {
+                sort: query_handle.sort,
+                descending: query_handle.descending,
+                ..Default::default()
+            })
+        }
+    };
     html! {
     <section class={classes!("w3-container", "w3-content")}>
         <h3>{props.title.clone()}</h3>
+        <div class={classes!("w3-row-padding")}>
+            <div class={classes!("w3-col", "l3", "m6", "s12")}>
+                <label>{"Sort by "}</label>
+                <select onchange={update_query(&query_handle, |q, v| {
+                    q.sort = v.parse().unwrap_or_default()
+                })}>
+                    {for SortKey::ALL.iter().map(|sort| html!{
+                        <option value={sort.key()} selected={*sort == query.sort}>
+                            {sort.to_string()}
+                        </option>
+                    })}
+                </select>
+                <select onchange={update_query(&query_handle, |q, v| q.descending = v == "descending")}>
+                    <option value="ascending" selected={!query.descending}>{"Ascending"}</option>
+                    <option value="descending" selected={query.descending}>{"Descending"}</option>
+                </select>
+            </div>
+            <div class={classes!("w3-col", "l3", "m6", "s12")}>
+                <label>{"Kind "}</label>
+                <select onchange={update_query(&query_handle, |q, v| {
+                    q.kind = match v.as_str() {
+                        "income" => Some(TransactionKind::Income),
+                        "expense" => Some(TransactionKind::Expense),
+                        _ => None,
+                    }
+                })}>
+                    <option value="" selected={query.kind.is_none()}>{"All"}</option>
+                    <option value="income" selected={query.kind == Some(TransactionKind::Income)}>
+                        {"Income"}
+                    </option>
+                    <option value="expense" selected={query.kind == Some(TransactionKind::Expense)}>
+                        {"Expense"}
+                    </option>
+                </select>
+            </div>
+            <div class={classes!("w3-col", "l3", "m6", "s12")}>
+                <label>{"From "}</label>
+                <input type="date"
+                    value={input_value(query.from)}
+                    onchange={update_query(&query_handle, |q, v| q.from = optional(&v))}
+                />
+                <label>{" to "}</label>
+                <input type="date"
+                    value={input_value(query.to)}
+                    onchange={update_query(&query_handle, |q, v| q.to = optional(&v))}
+                />
+            </div>
+            <div class={classes!("w3-col", "l3", "m6", "s12")}>
+                <label>{"Category "}</label>
+                // "=" marks a category, so that the uncategorized can be
+                // told apart from all
+                <select onchange={update_query(&query_handle, |q, v| {
+                    q.category = v.strip_prefix('=').map(str::to_owned)
+                })}>
+                    <option value="" selected={query.category.is_none()}>{"All"}</option>
+                    <option value="=" selected={query.category.as_deref() == Some("")}>
+                        {"Uncategorized"}
+                    </option>
+                    {for categories(&props.data, &[]).into_iter().map(|category| html!{
+                        <option
+                            value={format!("={category}")}
+                            selected={query.category.as_ref() == Some(&category)}
+                        >{category}</option>
+                    })}
+                </select>
+            </div>
+            <div class={classes!("w3-col", "l3", "m6", "s12")}>
+                <label>{"Show "}</label>
+                <select onchange={update_query(&query_handle, |q, v| {
+                    q.planned = match v.as_str() {
+                        "planned" => Some(true),
+                        "actual" => Some(false),
+                        _ => None,
+                    }
+                })}>
+                    <option value="" selected={query.planned.is_none()}>{"Planned and actual"}</option>
+                    <option value="planned" selected={query.planned == Some(true)}>{"Planned"}</option>
+                    <option value="actual" selected={query.planned == Some(false)}>{"Actual"}</option>
+                </select>
+            </div>
+            <div class={classes!("w3-col", "l3", "m6", "s12")}>
+                <label>{"Search "}</label>
+                <input type="search"
+                    placeholder="payee, memo, reference"
+                    value={query.search.clone()}
+                    onchange={update_query(&query_handle, |q, v| q.search = v)}
+                />
+            </div>
+            <div class={classes!("w3-col", "l3", "m6", "s12")}>
+                <label>{"Value "}</label>
+                <input type="number"
+                    placeholder="min"
+                    value={input_value(query.min_value)}
+                    onchange={update_query(&query_handle, |q, v| q.min_value = optional(&v))}
+                />
+                <input type="number"
+                    placeholder="max"
+                    value={input_value(query.max_value)}
+                    onchange={update_query(&query_handle, |q, v| q.max_value = optional(&v))}
+                />
+            </div>
+        </div>
+        <p>
+            {format!(
+                "{} of {} transactions: income {}, expenses {}, net {} ",
+                totals.count,
+                props.data.len(),
+                totals.income,
+                totals.expenses,
+                totals.net()
+            )}
+            <button onclick={clear_filters}>{"Clear filters"}</button>
+        </p>
+        {for props.period.iter().map(|period| {
+            let clear_period = props.clear_period.clone();
+            html!{
+            <p>
+                if period.num_days() == 1 {
+                    {"Showing transactions on "}{period.start()}{" "}
+                } else {
+                    {"Showing transactions from "}{period.start()}{" to "}{period.end()}{" "}
+                }
+                <button onclick={move |_| clear_period.emit(())}>{"Show all"}</button>
+            </p>
+            }
+        })}
+        if pages > 1 {
+            <p>
+                <button disabled={page == 0} onclick={go_to(0)}>{"First"}</button>
+                <button disabled={page == 0} onclick={go_to(page.saturating_sub(1))}>
+                    {"Previous"}
+                </button>
+                {format!(" Page {} of {pages} ", page + 1)}
+                <button disabled={page + 1 == pages} onclick={go_to(page + 1)}>{"Next"}</button>
+                <button disabled={page + 1 == pages} onclick={go_to(pages - 1)}>{"Last"}</button>
+            </p>
+        }
         <ol id="transactions-list">
-            {for props.data.iter().map(|tr| html!{
+            {for records.iter().skip(page * PAGE_SIZE).take(PAGE_SIZE).map(|tr| html!{
                 <TransactionsListItem
+                    key={tr.id}
                     value={tr.transaction.value}
                     kind={tr.transaction.kind}
                     date={tr.transaction.date}
+                    payee={tr.transaction.payee.clone()}
+                    memo={tr.transaction.memo.clone()}
+                    reference={tr.transaction.reference.clone()}
+                    category={tr.transaction.category.clone()}
+                    planned={tr.transaction.planned}
+                    fulfils={tr.transaction.fulfils}
+                    fulfilled_by={tr.fulfilled_by}
+                    status={tr.status}
                     id={tr.id}
                     delete_transaction={props.delete_transaction.clone()}
                 />
@@ -33,6 +258,14 @@ pub struct TransactionsListItemProps {
     value: Dollars,
     kind: TransactionKind,
     date: Date,
+    payee: String,
+    memo: String,
+    reference: Option<String>,
+    category: String,
+    planned: bool,
+    fulfils: Option<TransactionId>,
+    fulfilled_by: Option<TransactionId>,
+    status: TransactionStatus,
     id: TransactionId,
     delete_transaction: Callback<TransactionId>,
 }
@@ -45,12 +278,36 @@ fn transactions_list_item(props: &TransactionsListItemProps) -> Html {
     let id = props.id.clone();
     html! {
         <>
-        <p>{"Kind: "}{kind}</p>
+        <p>{"Kind: "}{kind}{if props.planned { " (planned)" } else { "" }}</p>
         <p>{"Date: "}{date}</p>
         <p>{"Value: "}{props.value}</p>
-        <button onclick={move |_| delete_transaction.emit(id)}>
-            {"Delete"}
-        </button>
+        if !props.payee.is_empty() {
+            <p>{"Payee: "}{props.payee.clone()}</p>
+        }
+        if !props.category.is_empty() {
+            <p>{"Category: "}{props.category.clone()}</p>
+        }
+        if let Some(reference) = props.reference.clone() {
+            <p>{"Reference: "}{reference}</p>
+        }
+        if !props.memo.is_empty() {
+            <p>{"Memo: "}{props.memo.clone()}</p>
+        }
+        if let Some(actual) = props.fulfilled_by {
+            <p>{format!("Fulfilled by #{actual}")}</p>
+        }
+        if let Some(plan) = props.fulfils {
+            <p>{format!("Fulfils plan #{plan}")}</p>
+        }
+        if props.status != TransactionStatus::Uncleared {
+            <p>{"Status: "}{props.status.to_string()}</p>
+        }
+        // reconciled transactions are locked
+        if props.status != TransactionStatus::Reconciled {
+            <button onclick={move |_| delete_transaction.emit(id)}>
+                {"Delete"}
+            </button>
+        }
         </>
     }
 }
@@ -0,0 +1,58 @@
+use financial_planner_core::stored_log;
+use financial_planner_core::vault::{Sealed, Vault};
+use financial_planner_core::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct UnlockScreenProps {
+    pub sealed: Sealed,
+    pub on_unlock: Callback<(Vault, Vec<Entry>)>,
+}
+
+#[function_component(UnlockScreen)]
+pub fn unlock_screen(props: &UnlockScreenProps) -> Html {
+    let passphrase_handle = use_state(String::default);
+    let error_handle = use_state(|| Option::<String>::None);
+
+    let on_passphrase_change = {
+        let passphrase_handle = passphrase_handle.clone();
+        move |e: Event| {
+            if let Some(input) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            {
+                passphrase_handle.set(input.value());
+            }
+        }
+    };
+
+    let unlock = {
+        let passphrase = (*passphrase_handle).clone();
+        let error_handle = error_handle.clone();
+        let sealed = props.sealed.clone();
+        let on_unlock = props.on_unlock.clone();
+        move |_| match stored_log::unlock(&passphrase, &sealed) {
+            Ok(unlocked) => on_unlock.emit(unlocked),
+            Err(e) => error_handle.set(Some(e.to_string())),
+        }
+    };
+
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        <h3>{"Unlock"}</h3>
+        <p>{"The stored log is encrypted. Enter the passphrase to open it."}</p>
+        <input onchange={on_passphrase_change}
+            type="password"
+            value={(*passphrase_handle).clone()}
+        />
+        <button onclick={unlock}>{"Unlock"}</button>
+        {if let Some(error) = (*error_handle).clone() {
+            html!{<p class={classes!("w3-text-red")}>{error}</p>}
+        } else {
+            html!{}
+        }}
+    </section>
+    }
+}
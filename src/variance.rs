@@ -0,0 +1,49 @@
+use financial_planner_core::*;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct VarianceReportProps {
+    pub title: String,
+    pub data: Vec<Variance>,
+}
+
+/// Planned against actual amounts by period and category, over the same
+/// periods as the timeline.
+#[function_component(VarianceReport)]
+pub fn variance_report(props: &VarianceReportProps) -> Html {
+    html! {
+    <section class={classes!("w3-container", "w3-content")}>
+        <h3>{props.title.clone()}</h3>
+        if props.data.is_empty() {
+            <p>{"No transactions in this range."}</p>
+        } else {
+            <table id="variance-report" class={classes!("w3-table", "w3-striped")}>
+                <tr>
+                    <th>{"Period"}</th>
+                    <th>{"Category"}</th>
+                    <th>{"Planned"}</th>
+                    <th>{"Actual"}</th>
+                    <th>{"Variance"}</th>
+                </tr>
+                {for props.data.iter().map(|v| {
+                    let period = if v.period.num_days() == 1 {
+                        v.period.start().to_string()
+                    } else {
+                        format!("{} to {}", v.period.start(), v.period.end())
+                    };
+                    let color = if v.variance() < 0 { "w3-text-red" } else { "" };
+                    html!{
+                    <tr>
+                        <td>{period}</td>
+                        <td>{if v.category.is_empty() { "Uncategorized" } else { &v.category }}</td>
+                        <td>{v.planned}</td>
+                        <td>{v.actual}</td>
+                        <td class={classes!(color)}>{v.variance()}</td>
+                    </tr>
+                    }
+                })}
+            </table>
+        }
+    </section>
+    }
+}
@@ -0,0 +1,271 @@
+use financial_planner_core::log_file::LogFile;
+use financial_planner_core::*;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Field {
+    Value,
+    Date,
+}
+
+/// the entry form, mirroring the web app's income and expense forms
+#[derive(Debug, PartialEq, Clone)]
+pub struct Form {
+    pub kind: TransactionKind,
+    pub value: String,
+    pub date: String,
+    pub field: Field,
+}
+
+impl Form {
+    fn new(kind: TransactionKind, date: Date) -> Self {
+        Self {
+            kind,
+            value: String::new(),
+            date: date.to_string(),
+            field: Field::Value,
+        }
+    }
+
+    fn input(&mut self) -> &mut String {
+        match self.field {
+            Field::Value => &mut self.value,
+            Field::Date => &mut self.date,
+        }
+    }
+
+    fn transaction(&self) -> Result<Transaction, String> {
+        let value = self
+            .value
+            .parse::<u16>()
+            .map_err(|e| format!("value: {e}"))?;
+        let date = self
+            .date
+            .parse::<Date>()
+            .map_err(|e| format!("date: {e}"))?;
+        Ok(Transaction::new(value as Dollars, self.kind, date))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Mode {
+    Browse,
+    Form(Form),
+}
+
+pub struct App {
+    pub log_file: LogFile,
+    /// index into `transaction_records` of the highlighted row
+    pub selected: usize,
+    pub mode: Mode,
+    pub message: Option<String>,
+    pub quit: bool,
+    today: Date,
+}
+
+impl App {
+    pub fn new(log_file: LogFile, today: Date) -> Self {
+        Self {
+            log_file,
+            selected: 0,
+            mode: Mode::Browse,
+            message: None,
+            quit: false,
+            today,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.message = None;
+        match &mut self.mode {
+            Mode::Browse => self.handle_browse_key(key),
+            Mode::Form(form) => match key.code {
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Tab | KeyCode::BackTab => {
+                    form.field = match form.field {
+                        Field::Value => Field::Date,
+                        Field::Date => Field::Value,
+                    }
+                }
+                KeyCode::Backspace => {
+                    form.input().pop();
+                }
+                KeyCode::Char(c) => form.input().push(c),
+                KeyCode::Enter => match form.transaction() {
+                    Ok(transaction) => {
+                        self.mode = Mode::Browse;
+                        self.append(Entry::Create(transaction));
+                    }
+                    Err(e) => self.message = Some(e),
+                },
+                _ => {}
+            },
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) {
+        let len = self.log_file.log.transaction_records().len();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(len.saturating_sub(1))
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('i') => {
+                self.mode = Mode::Form(Form::new(TransactionKind::Income, self.today))
+            }
+            KeyCode::Char('e') => {
+                self.mode = Mode::Form(Form::new(TransactionKind::Expense, self.today))
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(tr) = self.log_file.log.transaction_records().get(self.selected) {
+                    if tr.status == TransactionStatus::Reconciled {
+                        self.message = Some(format!("transaction {} is reconciled", tr.id));
+                        return;
+                    }
+                    self.append(Entry::Delete(tr.id));
+                    self.selected = self.selected.min(len.saturating_sub(2));
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Some(tr) = self.log_file.log.transaction_records().get(self.selected) {
+                    match tr.status {
+                        TransactionStatus::Reconciled => {
+                            self.message = Some(format!("transaction {} is reconciled", tr.id))
+                        }
+                        TransactionStatus::Cleared => self.append(Entry::SetCleared(tr.id, false)),
+                        TransactionStatus::Uncleared => self.append(Entry::SetCleared(tr.id, true)),
+                    }
+                }
+            }
+            KeyCode::Char('[') => self.shift_date_range(-1),
+            KeyCode::Char(']') => self.shift_date_range(1),
+            _ => {}
+        }
+    }
+
+    /// move the date range back or forward by its own length
+    fn shift_date_range(&mut self, direction: i64) {
+        let date_range = self.log_file.log.date_range();
+        match date_range.shift(direction * date_range.num_days()) {
+            Some(date_range) => self.append(Entry::SetDate(date_range)),
+            None => self.message = Some("date range out of bounds".to_owned()),
+        }
+    }
+
+    fn append(&mut self, entry: Entry) {
+        if let Err(e) = self.log_file.log.append(entry) {
+            self.message = Some(e.to_string());
+        } else if let Err(e) = self.log_file.save() {
+            self.message = Some(format!("save log: {e}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{App, Mode};
+    use financial_planner_core::log_file::LogFile;
+    use financial_planner_core::*;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+    /// an app saving into a directory of its own, removed when the
+    /// returned `TempDir` is dropped
+    fn app() -> (tempfile::TempDir, App) {
+        let dir = tempfile::tempdir().unwrap();
+        let log_file = LogFile::open(dir.path().join("log.yaml"), None).unwrap();
+        (dir, App::new(log_file, "2023-01-10".parse().unwrap()))
+    }
+
+    fn press(app: &mut App, codes: impl IntoIterator<Item = KeyCode>) {
+        for code in codes {
+            app.handle_key(KeyEvent::from(code));
+        }
+    }
+
+    fn type_str(app: &mut App, s: &str) {
+        press(app, s.chars().map(KeyCode::Char));
+    }
+
+    #[test]
+    fn test_add_expense() {
+        let (_dir, mut app) = app();
+        press(&mut app, [KeyCode::Char('e')]);
+        type_str(&mut app, "42");
+        press(&mut app, [KeyCode::Enter]);
+        assert_eq!(app.mode, Mode::Browse);
+        assert_eq!(
+            app.log_file.log.transaction_records(),
+            vec![TransactionRecord {
+                transaction: Transaction::new(
+                    42,
+                    TransactionKind::Expense,
+                    "2023-01-10".parse().unwrap()
+                ),
+                id: 0,
+                status: TransactionStatus::Uncleared,
+                fulfilled_by: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_form_stays_open() {
+        let (_dir, mut app) = app();
+        press(&mut app, [KeyCode::Char('i'), KeyCode::Tab]);
+        press(&mut app, std::iter::repeat_n(KeyCode::Backspace, 10));
+        type_str(&mut app, "not a date");
+        press(&mut app, [KeyCode::Char('1'), KeyCode::Enter]);
+        assert!(matches!(app.mode, Mode::Form(_)));
+        assert!(app.message.is_some());
+        assert!(app.log_file.log.transaction_records().is_empty());
+    }
+
+    #[test]
+    fn test_delete_selected() {
+        let (_dir, mut app) = app();
+        for value in ["1", "2"] {
+            press(&mut app, [KeyCode::Char('i')]);
+            type_str(&mut app, value);
+            press(&mut app, [KeyCode::Enter]);
+        }
+        press(&mut app, [KeyCode::Down, KeyCode::Char('d')]);
+        let records = app.log_file.log.transaction_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].transaction.value, 1);
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn test_clear() {
+        let (_dir, mut app) = app();
+        press(&mut app, [KeyCode::Char('i')]);
+        type_str(&mut app, "5");
+        press(&mut app, [KeyCode::Enter, KeyCode::Char('c')]);
+        assert_eq!(app.log_file.log.status(0), TransactionStatus::Cleared);
+        press(&mut app, [KeyCode::Char('c')]);
+        assert_eq!(app.log_file.log.status(0), TransactionStatus::Uncleared);
+        press(&mut app, [KeyCode::Char('c')]);
+        app.log_file
+            .log
+            .append(Entry::Reconcile(Reconciliation {
+                statement_date: "2023-01-10".parse().unwrap(),
+                statement_balance: 5,
+            }))
+            .unwrap();
+        press(&mut app, [KeyCode::Char('d')]);
+        assert!(app.message.is_some());
+        assert_eq!(app.log_file.log.transaction_records().len(), 1);
+    }
+
+    #[test]
+    fn test_shift_date_range() {
+        let (_dir, mut app) = app();
+        press(&mut app, [KeyCode::Char(']')]);
+        assert_eq!(
+            app.log_file.log.date_range(),
+            DateRange::new("2023-02-01".parse().unwrap(), "2023-03-03".parse().unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,50 @@
+mod app;
+mod ui;
+
+use app::App;
+use clap::Parser;
+use financial_planner_core::log_file::LogFile;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::{error::Error, path::PathBuf, process::ExitCode};
+
+/// Browse and edit a financial planner log file in the terminal. Reads and
+/// writes the same YAML format the web app exports.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// path to the log file; created on first write if it does not exist
+    #[arg(short, long, default_value = "log.yaml")]
+    file: PathBuf,
+    /// passphrase of an encrypted log; a new file is encrypted if given
+    #[arg(long, env = "FINANCIAL_PLANNER_PASSPHRASE", hide_env_values = true)]
+    passphrase: Option<String>,
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let log_file = LogFile::open(cli.file, cli.passphrase.as_deref())?;
+    let mut app = App::new(log_file, chrono::Local::now().date_naive());
+    let mut terminal = ratatui::init();
+    let result = (|| -> Result<(), Box<dyn Error>> {
+        while !app.quit {
+            terminal.draw(|frame| ui::draw(frame, &app))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key);
+                }
+            }
+        }
+        Ok(())
+    })();
+    ratatui::restore();
+    result
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::app::{App, Field, Mode};
+use financial_planner_core::*;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::Line,
+    widgets::{Axis, Block, Chart, Dataset, GraphType, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, bottom] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(frame.area());
+    let [list, chart] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+    let transaction_records = app.log_file.log.transaction_records();
+    let items = transaction_records.iter().map(|tr| {
+        let Transaction {
+            value,
+            kind,
+            date,
+            payee,
+            planned,
+            ..
+        } = &tr.transaction;
+        let color = match kind {
+            TransactionKind::Income => Color::Blue,
+            TransactionKind::Expense => Color::Red,
+        };
+        let status = match tr.status {
+            TransactionStatus::Uncleared => ' ',
+            TransactionStatus::Cleared => 'c',
+            TransactionStatus::Reconciled => 'R',
+        };
+        // "~" marks an estimate
+        let value = if *planned {
+            format!("~{value}")
+        } else {
+            value.to_string()
+        };
+        ListItem::new(
+            format!(
+                "{:>4}  {:<7}  {date}  {value:>8} {status}  {payee}",
+                tr.id,
+                kind.to_string()
+            )
+            .trim_end()
+            .to_owned(),
+        )
+        .style(Style::default().fg(color))
+    });
+    let mut list_state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title("Transactions"))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        list,
+        &mut list_state,
+    );
+
+    draw_balance_chart(frame, chart, &app.log_file.log);
+
+    let text = match &app.mode {
+        Mode::Browse => app.message.clone().unwrap_or_else(|| {
+            "[i] income  [e] expense  [d] delete  [c] clear  [j/k] move  [ / ] shift range  [q] quit"
+                .to_owned()
+        }),
+        Mode::Form(form) => {
+            let marker = |field| if form.field == field { ">" } else { " " };
+            let mut text = format!(
+                "{} form  {}value: {:<8} {}date: {:<10}  [tab] next field  [enter] submit  [esc] cancel",
+                form.kind,
+                marker(Field::Value),
+                form.value,
+                marker(Field::Date),
+                form.date,
+            );
+            if let Some(message) = &app.message {
+                text = format!("{text}  {message}");
+            }
+            text
+        }
+    };
+    frame.render_widget(Paragraph::new(text).block(Block::bordered()), bottom);
+}
+
+/// the balance of each day in the log's date range as a braille line
+fn draw_balance_chart(frame: &mut Frame, area: ratatui::layout::Rect, log: &Log) {
+    let date_range = log.date_range();
+    let data = log.timeline_data();
+    let points = data
+        .iter()
+        .enumerate()
+        .map(|(n, s)| (n as f64, s.balance as f64))
+        .collect::<Vec<_>>();
+    let min = data
+        .iter()
+        .map(|s| s.balance)
+        .min()
+        .unwrap_or_default()
+        .min(0) as f64;
+    let max = data
+        .iter()
+        .map(|s| s.balance)
+        .max()
+        .unwrap_or_default()
+        .max(0) as f64;
+    let (min, max) = if min == max {
+        (min, min + 100.0)
+    } else {
+        (min, max)
+    };
+    let zero = [(0.0, 0.0), (data.len().saturating_sub(1) as f64, 0.0)];
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(&zero),
+        Dataset::default()
+            .name("Balance")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::White))
+            .data(&points),
+    ];
+    let chart = Chart::new(datasets)
+        .block(Block::bordered().title(format!(
+            "Balance {} to {}",
+            date_range.start(),
+            date_range.end()
+        )))
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, data.len().saturating_sub(1) as f64])
+                .labels([
+                    Line::from(date_range.start().to_string()),
+                    Line::from(date_range.end().to_string()),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([min, max])
+                .labels([Line::from(format!("{min}")), Line::from(format!("{max}"))]),
+        );
+    frame.render_widget(chart, area);
+}
+
+#[cfg(test)]
+mod test {
+    use super::draw;
+    use crate::app::App;
+    use financial_planner_core::log_file::LogFile;
+    use financial_planner_core::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_draw() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log_file = LogFile::open(dir.path().join("log.yaml"), None).unwrap();
+        log_file
+            .log
+            .append(Entry::Create(Transaction::new(
+                100,
+                TransactionKind::Income,
+                "2023-01-02".parse().unwrap(),
+            )))
+            .unwrap();
+        let app = App::new(log_file, "2023-01-10".parse().unwrap());
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+        let screen = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(screen.contains("Income   2023-01-02"));
+        assert!(screen.contains("Balance 2023-01-01 to 2023-01-31"));
+    }
+}